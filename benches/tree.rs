@@ -0,0 +1,137 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use tree_ds::prelude::*;
+
+/// Build a tree with `size` nodes where every node has up to four children.
+fn build_tree(size: u32) -> Tree<u32, u32> {
+    let mut tree = Tree::new(Some("Benchmark Tree"));
+    tree.add_node(Node::new(0, Some(0)), None).unwrap();
+    for node_id in 1..size {
+        let parent_id = (node_id - 1) / 4;
+        tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent_id))
+            .unwrap();
+    }
+    tree
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for size in [1_000u32, 10_000, 100_000] {
+        let tree = build_tree(size);
+        let node_id = size - 1;
+        group.bench_with_input(BenchmarkId::new("index", size), &node_id, |b, node_id| {
+            b.iter(|| tree.get_node_by_id(black_box(node_id)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("linear_scan", size),
+            &node_id,
+            |b, node_id| b.iter(|| tree.get_nodes().get_by_node_id(black_box(node_id)).cloned()),
+        );
+    }
+    group.finish();
+}
+
+fn traversal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traversal");
+    group.sample_size(10);
+    for size in [1_000u32, 10_000, 100_000] {
+        let tree = build_tree(size);
+        group.bench_with_input(BenchmarkId::new("index", size), &tree, |b, tree| {
+            b.iter(|| tree.traverse(TraversalStrategy::PreOrder, black_box(&0)))
+        });
+        if size > 10_000 {
+            // A full traversal with linear lookups is quadratic and takes too long at this size.
+            continue;
+        }
+        group.bench_with_input(BenchmarkId::new("linear_scan", size), &tree, |b, tree| {
+            b.iter(|| {
+                // The traversal as it was done before the tree kept a lookup table.
+                let mut stack = vec![0u32];
+                let mut order = vec![];
+                while let Some(node_id) = stack.pop() {
+                    let node = tree.get_nodes().get_by_node_id(&node_id).unwrap();
+                    stack.extend(node.get_children_ids().into_iter().rev());
+                    order.push(node_id);
+                }
+                order
+            })
+        });
+    }
+    group.finish();
+}
+
+fn navigation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("navigation");
+    group.sample_size(10);
+    for size in [1_000u32, 10_000, 100_000] {
+        let tree = build_tree(size);
+        group.bench_with_input(BenchmarkId::new("ids", size), &tree, |b, tree| {
+            b.iter(|| {
+                let mut stack = vec![0u32];
+                let mut count = 0;
+                while let Some(node_id) = stack.pop() {
+                    let node = tree.get_node_by_id(&node_id).unwrap();
+                    stack.extend(node.get_children_ids());
+                    count += 1;
+                }
+                count
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("handles", size), &tree, |b, tree| {
+            b.iter(|| {
+                let mut stack = vec![tree.get_node_handle(&0).unwrap()];
+                let mut count = 0;
+                while let Some(handle) = stack.pop() {
+                    stack.extend(tree.get_children_handles(handle).unwrap());
+                    count += 1;
+                }
+                count
+            })
+        });
+    }
+    group.finish();
+}
+
+fn construction(c: &mut Criterion) {
+    let mut group = c.benchmark_group("construction");
+    group.sample_size(10);
+    for size in [1_000u32, 10_000, 100_000] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, size| {
+            b.iter(|| build_tree(black_box(*size)))
+        });
+    }
+    group.finish();
+}
+
+fn lowest_common_ancestor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lowest_common_ancestor");
+    for size in [1_000u32, 10_000, 100_000] {
+        let tree = build_tree(size);
+        let index = tree.build_lca_index().unwrap();
+        let nodes = (size - 1, size / 2);
+        group.bench_with_input(
+            BenchmarkId::new("tree", size),
+            &nodes,
+            |b, (first, second)| {
+                b.iter(|| tree.lowest_common_ancestor(black_box(first), black_box(second)))
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("index", size),
+            &nodes,
+            |b, (first, second)| {
+                b.iter(|| index.lowest_common_ancestor(black_box(first), black_box(second)))
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    lookup,
+    traversal,
+    navigation,
+    construction,
+    lowest_common_ancestor
+);
+criterion_main!(benches);
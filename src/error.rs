@@ -19,6 +19,25 @@ pub enum Error {
     /// The node was not found in the tree.
     #[cfg_attr(not(feature = "no_std"), error("Node {0} not found in the tree."))]
     NodeNotFound(String),
+    /// A node with the same id is already present in the tree.
+    #[cfg_attr(
+        not(feature = "no_std"),
+        error("Node {0} is already present in the tree.")
+    )]
+    DuplicateNodeId(String),
+    /// The input could not be parsed.
+    #[allow(clippy::enum_variant_names)]
+    #[cfg_attr(not(feature = "no_std"), error("Invalid input: {0}"))]
+    ParseError(String),
+    /// The graph read into a tree has a cycle.
+    #[cfg_attr(
+        not(feature = "no_std"),
+        error("The graph has a cycle through node {0}.")
+    )]
+    CycleDetected(String),
+    /// A node of the graph read into a tree has more than one parent.
+    #[cfg_attr(not(feature = "no_std"), error("Node {0} has more than one parent."))]
+    MultipleParents(String),
     /// An error occurred while formatting the output.
     #[allow(clippy::enum_variant_names)]
     #[cfg_attr(not(feature = "no_std"), error("{0}"))]
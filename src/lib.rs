@@ -42,23 +42,28 @@
 //!
 //! let node = Node::new(1, Some(2));
 //! ```
-//! However, you can enable the `auto_id` feature to generate IDs automatically. This is useful when
-//! you want to create a node without specifying the ID. For a node to be created with an auto-generated
-//! ID, the `Q` type must implement the `From<i32>` trait.
+//! However, you can enable the `auto_id` feature to let the tree generate the IDs. Every tree has its own
+//! id generator, which counts up from 1 unless you pick another one: `SequentialIds`, `RandomIds`,
+//! `PrefixedIds` or your own implementation of the `IdGenerator` trait. The `Q` type must implement the
+//! `GeneratedId` trait, which is implemented for the integer types and `String`.
 //!
 //! ```rust, ignore
 //! use tree_ds::prelude::*;
 //!
-//! let node = Node::<i32, &str>::new_with_auto_id(Some("Harry Doe"));
-//! let node_2 = Node::<i32, &str>::new_with_auto_id(Some("Jane Doe"));
-//! assert_ne!(node.get_node_id(), node_2.get_node_id());//!
+//! let mut tree: Tree<i32, &str> = Tree::new(Some("Sample Tree"));
+//! let root = tree.add_value(Some("Harry Doe"), None).unwrap();
+//! let child = tree.add_value(Some("Jane Doe"), Some(&root)).unwrap();
+//! assert_ne!(root, child);
 //! ```
 //!
 //! ## Traversal
-//! The tree supports three traversal strategies:
+//! The tree supports the following traversal strategies:
 //! - Pre-order
 //! - Post-order
 //! - In-order
+//! - Level-order
+//! - Reverse level-order
+//! - Zigzag level-order
 //!
 //! Consider the following tree:
 //! ```text
@@ -68,6 +73,9 @@
 //!        └── Node 4: 5
 //! ```
 //!
+//! The pre-order, post-order and level-order traversals are also available as lazy iterators through
+//! `Tree::iter_pre_order`, `Tree::iter_post_order` and `Tree::iter_level_order`.
+//!
 //! You can modify nodes during traversal by using the iterator from the returned traversal data.
 //!
 //! ```rust
@@ -104,6 +112,26 @@
 //!        └── Node 4: 6
 //! ```
 //!
+//! ## Forests
+//! A tree has a single root node. A tree created with `Tree::new_forest` is a forest instead and can
+//! have any number of root nodes. Nodes can be promoted to new root nodes with `Tree::promote_to_root`
+//! and merged back under another node with `Tree::move_node`.
+//!
+//! ```rust
+//! use tree_ds::prelude::*;
+//!
+//! # fn main() -> Result<()> {
+//! let mut forest = Tree::new_forest(Some("Sample Forest"));
+//! let root_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+//! let root_2 = forest.add_node(Node::new(2, Some(3)), None)?;
+//! forest.add_node(Node::new(3, Some(4)), Some(&root_2))?;
+//!
+//! assert_eq!(forest.get_root_nodes().len(), 2);
+//! assert_eq!(forest.traverse_all(TraversalStrategy::PreOrder)?, vec![1, 2, 3]);
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ## `no_std` Environments.
 //! This crate can be used in `no_std` environments by enabling the `no_std` feature.
 //!
@@ -115,9 +143,13 @@
 //! ## Cargo Features
 //! The following cargo features are also available:
 //! - By default the library is synchronous, and you need to manually provide ids for the nodes.
-//! - `async`: Enables support for async operations on the tree.
-//! - `serde`: Enables serialization and deserialization of the tree.
-//! - `auto_id`: Enables auto-generation of node IDs.
+//! - `async`: Makes the trees thread safe. The nodes are held behind a `RwLock` instead of a `RefCell`, so a
+//!   `Tree<Q, T>` is `Send` and `Sync` when `Q` and `T` are and can be shared between threads and tasks.
+//!   It also adds `Tree::stream`, a `futures_core::Stream` over a traversal, and `Tree::visit_async`, which
+//!   awaits a future for every node with bounded concurrency. Neither depends on a particular runtime.
+//! - `serde`: Enables serialization and deserialization of the tree. Trees are serialized as a flat list of nodes, or
+//!   nested with every node holding its children through the `nested` module and the `Nested` wrapper.
+//! - `auto_id`: Enables auto-generation of node IDs by per-tree id generators.
 //! - `no_std`: Disables the standard library.
 
 #![cfg_attr(feature = "no_std", no_std)]
@@ -128,21 +160,22 @@ extern crate alloc;
 mod lib {
     #[cfg(feature = "no_std")]
     pub use alloc::{
-        collections::BTreeSet,
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet, VecDeque},
         string::{String, ToString},
         vec,
         vec::Vec,
     };
-    #[cfg(all(test, feature = "no_std"))]
-        pub use alloc::format;
+    #[cfg(feature = "no_std")]
+    pub use alloc::format;
     #[cfg(all(feature = "no_std", not(feature = "async")))]
-    pub use alloc::rc::Rc;
+    pub use alloc::rc::{Rc, Weak};
     #[cfg(all(feature = "no_std", feature = "async"))]
-    pub use alloc::sync::Arc;
+    pub use alloc::sync::{Arc, Weak};
 
     #[cfg(not(feature = "no_std"))]
     pub use std::{
-        collections::HashSet,
+        collections::{HashMap, HashSet, VecDeque},
         string::{String, ToString},
         vec,
         vec::Vec,
@@ -150,21 +183,29 @@ mod lib {
     #[cfg(all(test, not(feature = "no_std")))]
     pub use std::format;
     #[cfg(all(not(feature = "no_std"), not(feature = "async")))]
-    pub use std::rc::Rc;
+    pub use std::rc::{Rc, Weak};
     #[cfg(all(not(feature = "no_std"), feature = "async"))]
-    pub use std::sync::Arc;
+    pub use std::sync::{Arc, Weak};
 
-    pub use self::core::cell::RefCell;
     pub use self::core::clone::Clone;
-    pub use self::core::cmp::{Eq, PartialEq};
+    pub use self::core::cmp::{Eq, Ordering, PartialEq};
     pub use self::core::convert::{AsRef, From};
     pub use self::core::default::Default;
-    pub use self::core::fmt::{Debug, Display, Error as FmtError, Formatter, Result as FmtResult};
+    pub use self::core::fmt::{
+        Debug, Display, Error as FmtError, Formatter, Result as FmtResult, Write,
+    };
     pub use self::core::hash::{Hash, Hasher};
+    pub use self::core::ops::Deref;
     pub use self::core::option::Option;
     pub use self::core::result::Result;
-    pub use self::core::slice::Iter;
-    pub use self::core::usize;
+    pub use self::core::slice::{self, Iter};
+    pub use self::core::str::FromStr;
+    #[cfg(feature = "async")]
+    pub use self::core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
 
     mod core {
         #[cfg(feature = "no_std")]
@@ -179,13 +220,30 @@ mod error;
 mod node;
 mod tree;
 
+#[cfg(feature = "serde")]
+pub use crate::tree::nested;
+
 pub mod prelude {
     //! A module to re-export the necessary types for the tree data structure.
 
     pub use crate::{
-        node::{Node, Nodes},
-        tree::{NodeRemovalStrategy, SubTree, TraversalStrategy, Tree},
+        node::{MaybeSync, Node, Nodes, ValueRef},
+        tree::{
+            ChildHandles, IndexMode, LcaIndex, LevelOrderIter, NodeHandle, NodeRemovalStrategy,
+            PostOrderIter, PreOrderIter, RepairPolicy, SearchAction, SubTree, TraversalStrategy,
+            Tree, TreeIterator, ValueIndex, Violation, WithDepth,
+        },
+    };
+
+    #[cfg(feature = "serde")]
+    pub use crate::tree::Nested;
+    #[cfg(feature = "async")]
+    pub use crate::tree::TraversalStream;
+    #[cfg(feature = "auto_id")]
+    pub use crate::tree::{
+        BuiltinIdGenerator, GeneratedId, IdGenerator, PrefixedIds, RandomIds, SequentialIds,
     };
+    pub use crate::tree::{DotOptions, NewickValue, RankDirection};
 
     /// The error type for this crate.
     pub type Result<T> = crate::lib::Result<T, crate::error::Error>;
@@ -1,16 +1,10 @@
-use lazy_static::lazy_static;
-use sequential_gen::prelude::{Generator, SimpleGenerator};
+use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
 
 use crate::lib::*;
-#[cfg(feature = "async")]
-use crate::lib::Arc;
-#[cfg(not(feature = "async"))]
-use crate::lib::Rc;
-use crate::node::{_Node, Node};
+use crate::node::Node;
 
-lazy_static! {
-    static ref GENERATOR: SimpleGenerator<usize> = SimpleGenerator::new(1usize);
-}
+/// The id of the next node created with `Node::new_with_auto_id`.
+static NEXT_NODE_ID: AtomicUsize = AtomicUsize::new(1);
 
 impl<Q, T> Node<Q, T>
 where
@@ -19,9 +13,13 @@ where
 {
     /// Creates a new node with an auto-generated ID.
     ///
-    /// The ID is generated using a sequence generator, meaning that the ID is sequential and unique.
-    /// This is useful when you want to create a node without specifying the ID. For a node to be
-    /// created with an auto-generated ID, the `Q` type must implement the `From<i32>` trait.
+    /// The ID is generated using a global sequence generator shared by the whole program, meaning that
+    /// the ID is sequential and unique. For a node to be created with an auto-generated ID, the `Q`
+    /// type must implement the `From<i32>` trait.
+    ///
+    /// The global sequence does not know about the id generators of the trees, so a node created
+    /// with this method can clash with a node added with `Tree::add_value`. Use `Tree::add_value`
+    /// instead, which takes the id from the id generator of the tree.
     ///
     /// # Arguments
     ///
@@ -34,6 +32,7 @@ where
     /// # Examples
     ///
     /// ```rust
+    /// # #![allow(deprecated)]
     /// # use tree_ds::prelude::*;
     ///
     /// let node = Node::<i32, &str>::new_with_auto_id(Some("Harry Doe"));
@@ -42,25 +41,12 @@ where
     /// ```
     ///
     /// This is available only when the `auto_id` feature is enabled.
+    #[deprecated(
+        note = "use `Tree::add_value`, which takes the id from the id generator of the tree"
+    )]
     pub fn new_with_auto_id(value: Option<T>) -> Self {
-        #[cfg(not(feature = "async"))]
-        {
-            Self(Rc::new(RefCell::new(_Node {
-                node_id: Q::from(GENERATOR.generate() as i32),
-                value,
-                children: vec![],
-                parent: None,
-            })))
-        }
-        #[cfg(feature = "async")]
-        {
-            Self(Arc::new(RefCell::new(_Node {
-                node_id: Q::from(GENERATOR.generate() as i32),
-                value,
-                children: vec![],
-                parent: None,
-            })))
-        }
+        let node_id = NEXT_NODE_ID.fetch_add(1, AtomicOrdering::Relaxed);
+        Self::new(Q::from(node_id as i32), value)
     }
 }
 
@@ -68,21 +54,12 @@ where
 mod tests {
     use super::*;
 
-    #[cfg(not(feature = "async"))]
     #[test]
+    #[allow(deprecated)]
     fn test_new_with_auto_id() {
         let node = Node::<i32, &str>::new_with_auto_id(Some("Harry Doe"));
         let node_2 = Node::<i32, &str>::new_with_auto_id(Some("Jane Doe"));
         assert_eq!(node.get_value(), Some("Harry Doe"));
         assert_ne!(node.get_node_id(), node_2.get_node_id());
     }
-
-    #[cfg(feature = "async")]
-    #[test]
-    fn test_new_with_auto_id_async() {
-        let node = Node::<i32, &str>::new_with_auto_id(Some("Harry Doe"));
-        let node_2 = Node::<i32, &str>::new_with_auto_id(Some("Jane Doe"));
-        assert_eq!(node.get_value(), Some("Harry Doe"));
-        assert_ne!(node.get_node_id(), node_2.get_node_id());
-    }
 }
@@ -0,0 +1,119 @@
+#[cfg(not(feature = "async"))]
+use core::cell::{Ref, RefCell, RefMut};
+#[cfg(all(feature = "async", not(feature = "no_std")))]
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(all(feature = "async", feature = "no_std"))]
+use spin::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::lib::*;
+
+/// A marker for the types that can be shared between the nodes of a tree.
+///
+/// With the `async` feature the nodes of a tree can be shared between threads, so these types must
+/// be `Send` and `Sync`. Without it, every type implements this trait.
+#[cfg(feature = "async")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "async")]
+impl<V> MaybeSync for V where V: Send + Sync + ?Sized {}
+
+/// A marker for the types that can be shared between the nodes of a tree.
+///
+/// With the `async` feature the nodes of a tree can be shared between threads, so these types must
+/// be `Send` and `Sync`. Without it, every type implements this trait.
+#[cfg(not(feature = "async"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "async"))]
+impl<V> MaybeSync for V where V: ?Sized {}
+
+/// A guard giving shared access to the value of a lock.
+#[cfg(not(feature = "async"))]
+pub(crate) type LockReadGuard<'a, V> = Ref<'a, V>;
+
+/// A guard giving exclusive access to the value of a lock.
+#[cfg(not(feature = "async"))]
+pub(crate) type LockWriteGuard<'a, V> = RefMut<'a, V>;
+
+/// A guard giving shared access to the value of a lock.
+#[cfg(feature = "async")]
+pub(crate) type LockReadGuard<'a, V> = RwLockReadGuard<'a, V>;
+
+/// A guard giving exclusive access to the value of a lock.
+#[cfg(feature = "async")]
+pub(crate) type LockWriteGuard<'a, V> = RwLockWriteGuard<'a, V>;
+
+/// The interior mutability used by the nodes of a tree.
+///
+/// This is a `RefCell` by default. With the `async` feature it is a `RwLock`, from `std` or from
+/// `spin` under `no_std`, so that the nodes are `Send` and `Sync`. A poisoned lock is still used.
+/// The only user code run under the lock is the function given to
+/// [`Node::with_value_mut`](crate::prelude::Node::with_value_mut), and a panic in it can leave the
+/// value half changed. That value is kept, like it is with a `RefCell`, and the node notifies its
+/// watchers of it while unwinding, so the value indexes of the tree still match the value.
+#[doc(hidden)]
+pub(crate) struct Lock<V> {
+    #[cfg(not(feature = "async"))]
+    cell: RefCell<V>,
+    #[cfg(feature = "async")]
+    cell: RwLock<V>,
+}
+
+impl<V> Lock<V> {
+    /// Create a new lock holding the given value.
+    pub(crate) fn new(value: V) -> Self {
+        #[cfg(not(feature = "async"))]
+        return Lock {
+            cell: RefCell::new(value),
+        };
+        #[cfg(feature = "async")]
+        return Lock {
+            cell: RwLock::new(value),
+        };
+    }
+
+    /// Get shared access to the value.
+    pub(crate) fn borrow(&self) -> LockReadGuard<'_, V> {
+        #[cfg(not(feature = "async"))]
+        return self.cell.borrow();
+        #[cfg(all(feature = "async", not(feature = "no_std")))]
+        return self.cell.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(all(feature = "async", feature = "no_std"))]
+        return self.cell.read();
+    }
+
+    /// Get exclusive access to the value.
+    pub(crate) fn borrow_mut(&self) -> LockWriteGuard<'_, V> {
+        #[cfg(not(feature = "async"))]
+        return self.cell.borrow_mut();
+        #[cfg(all(feature = "async", not(feature = "no_std")))]
+        return self.cell.write().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(all(feature = "async", feature = "no_std"))]
+        return self.cell.write();
+    }
+}
+
+impl<V> Debug for Lock<V>
+where
+    V: Debug,
+{
+    /// Format the value of the lock for debugging.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Lock")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+impl<V> PartialEq for Lock<V>
+where
+    V: PartialEq,
+{
+    /// Compare the values of two locks.
+    fn eq(&self, other: &Self) -> bool {
+        *self.borrow() == *other.borrow()
+    }
+}
+
+impl<V> Eq for Lock<V> where V: Eq {}
@@ -1,14 +1,18 @@
 #[cfg(feature = "serde")]
-use serde::{Deserialize, ser::SerializeStruct, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
-use crate::lib::*;
 #[cfg(feature = "async")]
 use crate::lib::Arc;
 #[cfg(not(feature = "async"))]
 use crate::lib::Rc;
+use crate::lib::*;
+
+pub use crate::node::lock::MaybeSync;
+pub(crate) use crate::node::lock::{Lock, LockReadGuard, LockWriteGuard};
 
 #[cfg(feature = "auto_id")]
 mod auto_id;
+mod lock;
 
 /// A node in a tree.
 ///
@@ -19,7 +23,7 @@ mod auto_id;
 /// # Type Parameters
 ///
 /// * `Q` - The type of the unique id of the node. Odd, I know but this is for flexibility. Some people might want to use
-/// a string as the unique id of the node. Others might want to use an integer. This is why the unique id is a generic type.
+///   a string as the unique id of the node. Others might want to use an integer. This is why the unique id is a generic type.
 /// * `T` - The type of the value of the node.
 ///
 /// # Fields
@@ -38,7 +42,7 @@ mod auto_id;
 /// ```
 #[cfg(not(feature = "async"))]
 #[derive(Clone, Debug, Eq)]
-pub struct Node<Q, T>(Rc<RefCell<_Node<Q, T>>>)
+pub struct Node<Q, T>(Rc<Lock<_Node<Q, T>>>)
 where
     Q: PartialEq + Eq + Clone,
     T: PartialEq + Eq + Clone;
@@ -52,7 +56,7 @@ where
 /// # Type Parameters
 ///
 /// * `Q` - The type of the unique id of the node. Odd, I know but this is for flexibility. Some people might want to use
-/// a string as the unique id of the node. Others might want to use an integer. This is why the unique id is a generic type.
+///   a string as the unique id of the node. Others might want to use an integer. This is why the unique id is a generic type.
 /// * `T` - The type of the value of the node.
 ///
 /// # Fields
@@ -62,6 +66,9 @@ where
 /// * `children` - The children of the node.
 /// * `parent` - The parent of the node.
 ///
+/// With the `async` feature the node is held behind a `RwLock`, so the node, and the trees holding it,
+/// are `Send` and `Sync` when `Q` and `T` are.
+///
 /// # Example
 ///
 /// ```rust
@@ -71,7 +78,7 @@ where
 /// ```
 #[cfg(feature = "async")]
 #[derive(Clone, Debug, Eq)]
-pub struct Node<Q, T>(Arc<RefCell<_Node<Q, T>>>)
+pub struct Node<Q, T>(Arc<Lock<_Node<Q, T>>>)
 where
     Q: PartialEq + Eq + Clone,
     T: PartialEq + Eq + Clone;
@@ -106,20 +113,22 @@ where
     pub fn new(node_id: Q, value: Option<T>) -> Self {
         #[cfg(not(feature = "async"))]
         {
-            Node(Rc::new(RefCell::new(_Node {
+            Node(Rc::new(Lock::new(_Node {
                 node_id,
                 value,
                 children: vec![],
                 parent: None,
+                watchers: Watchers::default(),
             })))
         }
         #[cfg(feature = "async")]
         {
-            Node(Arc::new(RefCell::new(_Node {
+            Node(Arc::new(Lock::new(_Node {
                 node_id,
                 value,
                 children: vec![],
                 parent: None,
+                watchers: Watchers::default(),
             })))
         }
     }
@@ -129,6 +138,11 @@ where
     /// This method adds a child to the node. The child is added to the children of the node and the parent
     /// of the child is set to the node.
     ///
+    /// On a node that is part of a tree this only changes the node. The tree keeps its own links, so
+    /// use the methods of the tree, like [`Tree::add_node`](crate::prelude::Tree::add_node), to change
+    /// its shape. [`Tree::validate`](crate::prelude::Tree::validate) reports such a change and
+    /// [`Tree::repair`](crate::prelude::Tree::repair) applies it to the tree.
+    ///
     /// # Arguments
     ///
     /// * `child` - The child to add to the node.
@@ -160,6 +174,10 @@ where
     /// of the child as an argument, we would have to get the child from the tree and then set the parent to `None`. And
     /// at this level we have no knowledge of the tree.
     ///
+    /// As with [`Node::add_child`], on a node that is part of a tree this only changes the node and not
+    /// the links of the tree until it is repaired. Use [`Tree::remove_node`](crate::prelude::Tree::remove_node)
+    /// to remove a node from a tree.
+    ///
     /// # Arguments
     ///
     /// * `child` - The child to remove from the node.
@@ -286,12 +304,141 @@ where
     /// assert_eq!(node.get_value(), Some(3));
     /// ```
     pub fn set_value(&self, value: Option<T>) {
-        self.0.borrow_mut().value = value;
+        let old_value = core::mem::replace(&mut self.0.borrow_mut().value, value);
+        self.notify_watchers(old_value.as_ref());
+    }
+
+    /// Read the value of the node without cloning it.
+    ///
+    /// This method calls the given function with a reference to the value of the node and returns the
+    /// result of the function. The node must not be changed from within the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function reading the value of the node.
+    ///
+    /// # Returns
+    ///
+    /// The result of the function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(String::from("Harry Doe")));
+    /// let length = node.with_value(|value| value.map_or(0, |name| name.len()));
+    /// assert_eq!(length, 9);
+    /// ```
+    pub fn with_value<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(Option<&T>) -> R,
+    {
+        f(self.0.borrow().value.as_ref())
+    }
+
+    /// Change the value of the node in place.
+    ///
+    /// This method calls the given function with a mutable reference to the value of the node and
+    /// returns the result of the function. The function can change, set or take the value. The node
+    /// must not be accessed from within the function. If the function panics, the value keeps the
+    /// changes made so far and the value indexes of the tree are updated with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function changing the value of the node.
+    ///
+    /// # Returns
+    ///
+    /// The result of the function.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// let old_value = node.with_value_mut(|value| value.replace(3));
+    /// assert_eq!(old_value, Some(2));
+    /// assert_eq!(node.get_value(), Some(3));
+    /// ```
+    pub fn with_value_mut<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut Option<T>) -> R,
+    {
+        let mut notice = ValueChangeNotice(self.0.borrow_mut());
+        // The watchers forget the old value before the change instead of being handed a copy of it
+        // afterwards. The notice gives them the new value when it is dropped, even if the function
+        // panics, so they also learn about a value left half changed by a panic.
+        for watcher in notice.0.watchers.upgrade() {
+            watcher.value_removed(&notice.0.node_id, notice.0.value.as_ref());
+        }
+        f(&mut notice.0.value)
+    }
+
+    /// Get a reference to the value of the node.
+    ///
+    /// This method returns a guard dereferencing to the value of the node. The node cannot be changed
+    /// while the guard is alive.
+    ///
+    /// # Returns
+    ///
+    /// The guard holding a reference to the value of the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(String::from("Harry Doe")));
+    /// let value = node.value_ref();
+    /// assert_eq!(value.as_deref(), Some("Harry Doe"));
+    /// ```
+    pub fn value_ref(&self) -> ValueRef<'_, Q, T> {
+        ValueRef(self.0.borrow())
+    }
+
+    /// Update the value of the node in place.
+    ///
+    /// This method calls the given function with a mutable reference to the value of the node. It does
+    /// nothing if the node has no value. The node must not be accessed from within the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function updating the value of the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(vec![2]));
+    /// node.update_value(|value| value.push(3));
+    /// assert_eq!(node.get_value(), Some(vec![2, 3]));
+    /// ```
+    pub fn update_value<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.with_value_mut(|value| {
+            if let Some(value) = value {
+                f(value);
+            }
+        });
+    }
+
+    /// Notify the watchers of the node that its value changed.
+    fn notify_watchers(&self, old_value: Option<&T>) {
+        let node = self.0.borrow();
+        for watcher in node.watchers.upgrade() {
+            watcher.value_changed(&node.node_id, old_value, node.value.as_ref());
+        }
     }
 
     /// Set the parent of the node.
     ///
-    /// This method sets the parent of the node.
+    /// This method sets the parent of the node. As with [`Node::add_child`], on a node that is part of
+    /// a tree this only changes the node and not the links of the tree until it is repaired.
     ///
     /// # Arguments
     ///
@@ -313,6 +460,63 @@ where
         }
         self.0.borrow_mut().parent = parent.map(|x| x.get_node_id());
     }
+
+    /// Create a copy of the node that does not share its state with the node.
+    ///
+    /// Cloning a node gives another handle to the same node, so setting the value of the clone also
+    /// sets the value of the node. This method copies the id, the value, the children ids and the
+    /// parent id of the node into a new node instead.
+    ///
+    /// # Returns
+    ///
+    /// The copy of the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Node;
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// let copy = node.deep_clone();
+    /// copy.set_value(Some(3));
+    /// assert_eq!(node.get_value(), Some(2));
+    /// assert_eq!(copy.get_value(), Some(3));
+    /// ```
+    pub fn deep_clone(&self) -> Self {
+        let node = self.0.borrow().clone();
+        #[cfg(not(feature = "async"))]
+        return Node(Rc::new(Lock::new(node)));
+        #[cfg(feature = "async")]
+        return Node(Arc::new(Lock::new(node)));
+    }
+
+    /// Set the parent id of the node without updating the children of any other node.
+    pub(crate) fn set_parent_id(&self, parent_id: Option<Q>) {
+        self.0.borrow_mut().parent = parent_id;
+    }
+
+    /// Replace the children ids of the node without updating the parent of any other node.
+    pub(crate) fn set_children_ids(&self, children: Vec<Q>) {
+        self.0.borrow_mut().children = children;
+    }
+
+    /// Insert a child id at the given position, capped at the number of children, without updating
+    /// the parent of any other node.
+    pub(crate) fn insert_child_id(&self, index: usize, child_id: Q) {
+        let mut node = self.0.borrow_mut();
+        let index = index.min(node.children.len());
+        node.children.insert(index, child_id);
+    }
+
+    /// Notify a watcher whenever the value of the node changes. A watcher is only registered once.
+    pub(crate) fn watch(&self, watcher: &Weak<dyn ValueWatcher<Q, T>>) {
+        self.0.borrow_mut().watchers.add(watcher);
+    }
+
+    /// Stop notifying a watcher when the value of the node changes.
+    pub(crate) fn unwatch(&self, watcher: &Weak<dyn ValueWatcher<Q, T>>) {
+        self.0.borrow_mut().watchers.remove(watcher);
+    }
 }
 
 impl<Q, T> PartialEq for Node<Q, T>
@@ -322,7 +526,8 @@ where
 {
     /// Compare two nodes for equality.
     fn eq(&self, other: &Self) -> bool {
-        self.get_node_id() == other.get_node_id() && self.get_value() == other.get_value()
+        let (node, other) = (self.0.borrow(), other.0.borrow());
+        node.node_id == other.node_id && node.value == other.value
     }
 }
 
@@ -390,9 +595,9 @@ where
         let node: _Node<Q, T> = Deserialize::deserialize(deserializer)?;
 
         #[cfg(not(feature = "async"))]
-        return Ok(Node(Rc::new(RefCell::new(node))));
+        return Ok(Node(Rc::new(Lock::new(node))));
         #[cfg(feature = "async")]
-        return Ok(Node(Arc::new(RefCell::new(node))));
+        return Ok(Node(Arc::new(Lock::new(node))));
     }
 }
 
@@ -412,6 +617,191 @@ where
     children: Vec<Q>,
     /// The parent of the node.
     parent: Option<Q>,
+    /// The value indexes to notify when the value of the node changes.
+    #[cfg_attr(feature = "serde", serde(skip, default = "Watchers::default"))]
+    watchers: Watchers<Q, T>,
+}
+
+/// An observer of the values of nodes, such as a value index of a tree.
+#[doc(hidden)]
+pub(crate) trait ValueWatcher<Q, T>: MaybeSync
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Record the value of a node that started being watched.
+    fn value_added(&self, node_id: &Q, value: Option<&T>);
+
+    /// Forget the value of a node that stopped being watched.
+    fn value_removed(&self, node_id: &Q, value: Option<&T>);
+
+    /// Update the value of a watched node.
+    fn value_changed(&self, node_id: &Q, old_value: Option<&T>, new_value: Option<&T>) {
+        self.value_removed(node_id, old_value);
+        self.value_added(node_id, new_value);
+    }
+
+    /// Find a node that cannot be added along with the others without breaking the watcher. It
+    /// returns the id of the node and the id of the node it clashes with.
+    fn find_conflict(&self, nodes: &[Node<Q, T>]) -> Option<(Q, Q)>;
+
+    /// Find the watched nodes that clash with another watched node. It returns the id of every such
+    /// node along with the id of the node it clashes with.
+    fn find_duplicates(&self) -> Vec<(Q, Q)>;
+}
+
+/// A list of weak references to value watchers.
+///
+/// Cloning the list gives an empty list: a copy of a node or of a tree is not watched by the watchers
+/// of the original. The list takes no part in comparisons.
+#[doc(hidden)]
+pub(crate) struct Watchers<Q, T>(Vec<Weak<dyn ValueWatcher<Q, T>>>)
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone;
+
+impl<Q, T> Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Add a watcher to the list unless it is already present. Dropped watchers are pruned.
+    pub(crate) fn add(&mut self, watcher: &Weak<dyn ValueWatcher<Q, T>>) {
+        self.0.retain(|w| w.strong_count() > 0);
+        if !self.0.iter().any(|w| Weak::ptr_eq(w, watcher)) {
+            self.0.push(watcher.clone());
+        }
+    }
+
+    /// Remove a watcher from the list.
+    pub(crate) fn remove(&mut self, watcher: &Weak<dyn ValueWatcher<Q, T>>) {
+        self.0.retain(|w| !Weak::ptr_eq(w, watcher));
+    }
+
+    /// Get the watchers that are still alive.
+    #[cfg(not(feature = "async"))]
+    pub(crate) fn upgrade(&self) -> Vec<Rc<dyn ValueWatcher<Q, T>>> {
+        self.0.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    /// Get the watchers that are still alive.
+    #[cfg(feature = "async")]
+    pub(crate) fn upgrade(&self) -> Vec<Arc<dyn ValueWatcher<Q, T>>> {
+        self.0.iter().filter_map(Weak::upgrade).collect()
+    }
+
+    /// Check if the list is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the weak references to the watchers.
+    pub(crate) fn iter(&self) -> Iter<'_, Weak<dyn ValueWatcher<Q, T>>> {
+        self.0.iter()
+    }
+}
+
+impl<Q, T> Default for Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    fn default() -> Self {
+        Watchers(vec![])
+    }
+}
+
+impl<Q, T> Clone for Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    fn clone(&self) -> Self {
+        Watchers::default()
+    }
+}
+
+impl<Q, T> Debug for Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Watchers({})", self.0.len())
+    }
+}
+
+impl<Q, T> PartialEq for Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<Q, T> Eq for Watchers<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+}
+
+/// A pending notice to the watchers of a node that its value changed.
+///
+/// The notice holds the node locked while its value changes. The watchers are given the new value
+/// when the notice is dropped, which also happens while unwinding from a panic in the function
+/// changing the value, and the node is unlocked afterwards.
+struct ValueChangeNotice<'a, Q, T>(LockWriteGuard<'a, _Node<Q, T>>)
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone;
+
+impl<Q, T> Drop for ValueChangeNotice<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Notify the watchers of the node.
+    fn drop(&mut self) {
+        for watcher in self.0.watchers.upgrade() {
+            watcher.value_added(&self.0.node_id, self.0.value.as_ref());
+        }
+    }
+}
+
+/// A reference to the value of a node.
+///
+/// This struct is returned by [`Node::value_ref`] and dereferences to the value of the node. The node
+/// cannot be changed while the reference is alive.
+pub struct ValueRef<'a, Q, T>(LockReadGuard<'a, _Node<Q, T>>)
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone;
+
+impl<Q, T> Deref for ValueRef<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    type Target = Option<T>;
+
+    /// Get the value of the node.
+    fn deref(&self) -> &Self::Target {
+        &self.0.value
+    }
+}
+
+impl<Q, T> Debug for ValueRef<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone + Debug,
+{
+    /// Format the value of the node for debugging.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(&self.0.value, f)
+    }
 }
 
 /// An iterator over the nodes in a tree.
@@ -425,7 +815,7 @@ where
 /// * `T` - The type of the value of the node.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct Nodes<Q, T>(Vec<Node<Q, T>>)
+pub struct Nodes<Q, T>(pub(crate) Vec<Node<Q, T>>)
 where
     Q: PartialEq + Eq + Clone,
     T: PartialEq + Eq + Clone;
@@ -477,7 +867,7 @@ where
     ///     // Do something with the node.
     /// }
     /// ```
-    pub fn iter(&self) -> Iter<Node<Q, T>> {
+    pub fn iter(&self) -> Iter<'_, Node<Q, T>> {
         self.0.iter()
     }
 
@@ -829,6 +1219,36 @@ mod tests {
         assert_eq!(node.get_value(), Some(3));
     }
 
+    #[test]
+    fn test_node_with_value() {
+        let node = Node::new(1, Some(vec![2, 3]));
+        assert_eq!(
+            node.with_value(|value| value.map(|value| value.len())),
+            Some(2)
+        );
+        assert_eq!(node.value_ref().as_deref(), Some(&[2, 3][..]));
+        let removed = node.with_value_mut(|value| value.as_mut().and_then(|value| value.pop()));
+        assert_eq!(removed, Some(3));
+        node.update_value(|value| value.push(4));
+        assert_eq!(node.get_value(), Some(vec![2, 4]));
+        assert_eq!(node.with_value_mut(Option::take), Some(vec![2, 4]));
+        node.update_value(|value| value.push(5));
+        assert!(node.value_ref().is_none());
+    }
+
+    #[test]
+    fn test_node_deep_clone() {
+        let node = Node::new(1, Some(2));
+        node.add_child(Node::new(2, Some(3)));
+        let copy = node.deep_clone();
+        assert_eq!(copy, node);
+        assert_eq!(copy.get_children_ids(), vec![2]);
+        copy.set_value(Some(4));
+        copy.add_child(Node::new(3, Some(5)));
+        assert_eq!(node.get_value(), Some(2));
+        assert_eq!(node.get_children_ids(), vec![2]);
+    }
+
     #[test]
     fn test_node_set_parent() {
         let parent_node = Node::new(1, Some(2));
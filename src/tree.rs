@@ -1,10 +1,48 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::error::Error::{InvalidOperation, NodeNotFound, RootNodeAlreadyPresent};
+use crate::error::Error::{
+    DuplicateNodeId, InvalidOperation, NodeNotFound, RootNodeAlreadyPresent,
+};
 use crate::lib::*;
-use crate::node::Nodes;
+use crate::node::{Nodes, Watchers};
 use crate::prelude::{Node, Result};
+use crate::tree::arena::Arena;
+pub use crate::tree::arena::{ChildHandles, NodeHandle};
+#[cfg(feature = "auto_id")]
+use crate::tree::auto_id::TreeIdGenerator;
+#[cfg(feature = "auto_id")]
+pub use crate::tree::auto_id::{
+    BuiltinIdGenerator, GeneratedId, IdGenerator, PrefixedIds, RandomIds, SequentialIds,
+};
+pub use crate::tree::dot::{DotOptions, RankDirection};
+pub use crate::tree::iter::{LevelOrderIter, PostOrderIter, PreOrderIter, TreeIterator, WithDepth};
+pub use crate::tree::lca::LcaIndex;
+#[cfg(feature = "serde")]
+pub use crate::tree::nested::Nested;
+pub use crate::tree::newick::NewickValue;
+pub use crate::tree::search::SearchAction;
+#[cfg(feature = "async")]
+pub use crate::tree::stream::TraversalStream;
+pub use crate::tree::validate::{RepairPolicy, Violation};
+pub use crate::tree::value_index::{IndexMode, ValueIndex};
+
+mod arena;
+#[cfg(feature = "auto_id")]
+mod auto_id;
+mod display;
+mod dot;
+mod iter;
+mod lca;
+#[cfg(feature = "serde")]
+pub mod nested;
+mod newick;
+mod search;
+#[cfg(feature = "async")]
+mod stream;
+mod transform;
+mod validate;
+mod value_index;
 
 /// The strategy to use when removing a node from the tree.
 ///
@@ -38,6 +76,15 @@ pub enum TraversalStrategy {
     /// Traverse the tree in in-order. This means that the left child is visited first, then the root node,
     /// and then the right child.
     InOrder,
+    /// Traverse the tree in level-order (breadth-first). This means that the root node is visited first,
+    /// then all the nodes at depth 1 from left to right, then all the nodes at depth 2 and so on.
+    LevelOrder,
+    /// Traverse the tree in reverse level-order. This means that the deepest nodes are visited first
+    /// and the root node is visited last. The nodes on the same level are visited from left to right.
+    ReverseLevelOrder,
+    /// Traverse the tree in zigzag level-order. This means that the levels are visited from the root
+    /// downwards, alternating between left to right and right to left, starting with left to right.
+    ZigZag,
 }
 
 /// A subtree of a tree.
@@ -55,6 +102,11 @@ pub type SubTree<Q, T> = Tree<Q, T>;
 /// orders such as pre-order, post-order, and in-order. The tree can be named for easy identification
 /// when working with multiple trees or subtrees.
 ///
+/// A tree created with [`Tree::new_forest`] is a forest: it can have more than one root node, each
+/// root node being the top of its own tree.
+///
+/// Cloning a tree copies its nodes, so changing the clone does not change the original.
+///
 /// # Type Parameters
 ///
 /// * `Q` - The type of the node id.
@@ -67,8 +119,8 @@ pub type SubTree<Q, T> = Tree<Q, T>;
 ///
 /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
 /// ```
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[derive(Clone)]
 pub struct Tree<Q, T>
 where
     Q: PartialEq + Eq + Clone,
@@ -76,9 +128,37 @@ where
 {
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     name: Option<String>,
-    nodes: Nodes<Q, T>,
+    /// The nodes of the tree along with the links between them, serialized as the list of nodes.
+    #[cfg_attr(feature = "serde", serde(rename = "nodes"))]
+    arena: Arena<Q, T>,
+    /// Whether the tree is a forest that can have more than one root node.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "core::ops::Not::not"))]
+    forest: bool,
+    /// The value indexes kept up to date by the tree. Clones of the tree do not maintain them.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    value_indexes: Watchers<Q, T>,
+    /// The generator of the ids of the nodes added with `Tree::add_value`.
+    #[cfg(feature = "auto_id")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    id_generator: Option<TreeIdGenerator<Q>>,
 }
 
+/// A map keyed by node ids.
+#[cfg(not(feature = "no_std"))]
+type IdMap<Q, V> = HashMap<Q, V>;
+
+/// A map keyed by node ids.
+#[cfg(feature = "no_std")]
+type IdMap<Q, V> = BTreeMap<Q, V>;
+
+/// A set of node ids.
+#[cfg(not(feature = "no_std"))]
+type IdSet<Q> = HashSet<Q>;
+
+/// A set of node ids.
+#[cfg(feature = "no_std")]
+type IdSet<Q> = BTreeSet<Q>;
+
 impl<Q, T> Tree<Q, T>
 where
     Q: PartialEq + Eq + Clone + Display + Hash + Ord,
@@ -102,60 +182,50 @@ where
     pub fn new(tree_name: Option<&str>) -> Self {
         Self {
             name: tree_name.map(|x| x.to_string()),
-            nodes: Nodes::default(),
+            arena: Arena::new(),
+            forest: false,
+            value_indexes: Watchers::default(),
+            #[cfg(feature = "auto_id")]
+            id_generator: None,
         }
     }
 
-    /// Add a node to the tree.
-    ///
-    /// This method adds a node to the tree. The node is added as a child of the parent node with the
-    /// given parent id. If the parent id is `None`, the node is added as a root node. The node id is
-    /// used to identify the node and the value is the value of the node. The value can be used to store
-    /// any data that you want to associate with the node.
-    ///
-    /// # Arguments
+    /// Create a new forest.
     ///
-    /// * `node_id` - The id of the node.
-    /// * `value` - The value of the node.
-    /// * `parent_id` - The id of the parent node. If `None`, the node is added as a root node.
+    /// This method creates a new tree with no nodes that can have more than one root node. Every node
+    /// added without a parent becomes a new root node.
     ///
     /// # Returns
     ///
-    /// The id of the node that was added to the tree. However, if no parent id is provided and the tree already
-    /// has a root node, an error is returned.
+    /// A new forest with no nodes.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Tree, Node};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_id = tree.add_node(Node::new(1, Some(2)), None);
+    /// # use tree_ds::prelude::{Node, Tree};
     ///
-    /// assert!(node_id.is_ok());
-    /// // This should return an error because the tree already has a root node.
-    /// let another_node_id = tree.add_node(Node::new(2, Some(3)), None);
-    /// assert!(another_node_id.is_err());
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut forest: Tree<i32, i32> = Tree::new_forest(Some("Sample Forest"));
+    /// forest.add_node(Node::new(1, Some(2)), None)?;
+    /// forest.add_node(Node::new(2, Some(3)), None)?;
+    /// assert_eq!(forest.get_root_nodes().len(), 2);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn add_node(&mut self, node: Node<Q, T>, parent_id: Option<&Q>) -> Result<Q> {
-        if let Some(parent_id) = parent_id {
-            if let Some(parent) = self.nodes.iter().find(|n| &n.get_node_id() == parent_id) {
-                parent.add_child(node.clone());
-            }
-        } else if self.get_root_node().is_some() {
-            return Err(RootNodeAlreadyPresent);
-        }
-        self.nodes.push(node.clone());
-        Ok(node.get_node_id())
+    pub fn new_forest(tree_name: Option<&str>) -> Self {
+        let mut tree = Self::new(tree_name);
+        tree.forest = true;
+        tree
     }
 
-    /// Get the name of the tree.
+    /// Check if the tree is a forest.
     ///
-    /// This method gets the name of the tree.
+    /// This method checks if the tree was created with [`Tree::new_forest`] and can therefore have more
+    /// than one root node.
     ///
     /// # Returns
     ///
-    /// The name of the tree.
+    /// `true` if the tree is a forest, `false` otherwise.
     ///
     /// # Example
     ///
@@ -163,476 +233,1796 @@ where
     /// # use tree_ds::prelude::Tree;
     ///
     /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
+    /// assert!(!tree.is_forest());
+    /// let forest: Tree<i32, i32> = Tree::new_forest(Some("Sample Forest"));
+    /// assert!(forest.is_forest());
     /// ```
-    pub fn get_name(&self) -> Option<&str> {
-        self.name.as_deref()
+    pub fn is_forest(&self) -> bool {
+        self.forest
     }
 
-    /// Set the name of the tree.
+    /// Create a tree from nodes that are already linked to each other, without checking them.
     ///
-    /// This method sets the name of the tree.
+    /// This method stores the given nodes as they are and links them to each other using the ids of
+    /// their parents and children. Unlike deserializing a tree, it does not check that the parent and children links of
+    /// the nodes agree, that the ids are unique or that the tree has a single root node, so it is
+    /// meant for trusted data. Use [`Tree::validate`] to check the tree afterwards.
     ///
     /// # Arguments
     ///
-    /// * `name` - The name of the tree.
+    /// * `tree_name` - The name of the tree.
+    /// * `nodes` - The nodes of the tree.
+    /// * `forest` - Whether the tree is a forest that can have more than one root node.
+    ///
+    /// # Returns
+    ///
+    /// The tree holding the nodes.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::Tree;
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// tree.rename(Some("New Name"));
-    /// assert_eq!(tree.get_name(), Some("New Name"));
+    /// # use tree_ds::prelude::{Node, Nodes, Tree};
+    ///
+    /// let root = Node::new(1, Some(2));
+    /// let child = Node::new(2, Some(3));
+    /// root.add_child(child.clone());
+    /// let tree = Tree::from_unchecked_parts(Some("Sample Tree"), Nodes::new(vec![root, child]), false);
+    /// assert!(tree.validate().is_empty());
+    /// assert_eq!(tree.get_node_by_id(&2).unwrap().get_parent_id(), Some(1));
     /// ```
-    pub fn rename(&mut self, name: Option<&str>) {
-        self.name = name.map(|x| x.to_string());
+    pub fn from_unchecked_parts(tree_name: Option<&str>, nodes: Nodes<Q, T>, forest: bool) -> Self {
+        let mut tree = Self::from_parts(tree_name.map(|x| x.to_string()), nodes);
+        tree.forest = forest;
+        tree
     }
 
-    /// Get a node in the tree.
+    /// Create a tree from its parts.
     ///
-    /// This method gets the node with the given node id in the tree.
+    /// This method creates a tree with the given name and nodes, linking the nodes to each other using
+    /// the ids of their parents and children.
+    #[doc(hidden)]
+    fn from_parts(name: Option<String>, nodes: Nodes<Q, T>) -> Self {
+        let mut tree = Self {
+            name,
+            arena: Arena::new(),
+            forest: false,
+            value_indexes: Watchers::default(),
+            #[cfg(feature = "auto_id")]
+            id_generator: None,
+        };
+        tree.store_nodes(nodes.iter().cloned());
+        tree
+    }
+
+    /// Store nodes in the tree.
+    ///
+    /// This method adds the nodes to the arena and then links them to each other using the ids of
+    /// their parents and children. Links to nodes that are not in the tree are left out. It returns
+    /// the handles of the stored nodes.
+    #[doc(hidden)]
+    fn store_nodes<I>(&mut self, nodes: I) -> Vec<NodeHandle>
+    where
+        I: IntoIterator<Item = Node<Q, T>>,
+    {
+        let handles = nodes
+            .into_iter()
+            .map(|node| {
+                self.track_node(&node);
+                self.arena.insert(node)
+            })
+            .collect::<Vec<_>>();
+        for handle in handles.iter() {
+            self.arena.link_from_ids(*handle);
+        }
+        handles
+    }
+
+    /// Remove nodes from the storage of the tree.
+    ///
+    /// This method removes the nodes from the arena, invalidating their handles, and stops tracking
+    /// them in the value indexes. It returns the removed nodes.
+    #[doc(hidden)]
+    fn discard_nodes(&mut self, handles: &[NodeHandle]) -> Vec<Node<Q, T>> {
+        let nodes = self.arena.remove_all(handles);
+        for node in nodes.iter() {
+            self.untrack_node(node);
+        }
+        nodes
+    }
+
+    /// Get the handle of a node from its id, or an error if the node is not in the tree.
+    #[doc(hidden)]
+    fn find_handle(&self, node_id: &Q) -> Result<NodeHandle> {
+        self.arena
+            .get_handle(node_id)
+            .ok_or(NodeNotFound(node_id.to_string()))
+    }
+
+    /// Get the id of the node with the given handle.
+    #[doc(hidden)]
+    fn id_of(&self, handle: NodeHandle) -> Option<Q> {
+        self.arena.get(handle).map(Node::get_node_id)
+    }
+
+    /// Get the ids of the children of the node with the given handle.
+    #[doc(hidden)]
+    fn children_ids(&self, handle: NodeHandle) -> Vec<Q> {
+        self.arena
+            .children(handle)
+            .into_iter()
+            .flatten()
+            .filter_map(|child| self.id_of(child))
+            .collect()
+    }
+
+    /// Add a node to the tree.
+    ///
+    /// This method adds a node to the tree. The node is added as a child of the parent node with the
+    /// given parent id. If the parent id is `None`, the node is added as a root node. The node id is
+    /// used to identify the node and the value is the value of the node. The value can be used to store
+    /// any data that you want to associate with the node.
     ///
     /// # Arguments
     ///
     /// * `node_id` - The id of the node.
+    /// * `value` - The value of the node.
+    /// * `parent_id` - The id of the parent node. If `None`, the node is added as a root node.
     ///
     /// # Returns
     ///
-    /// The node with the given node id in the tree or `None` if the node is not found.
+    /// The id of the node that was added to the tree. An error is returned if:
+    /// - No parent id is provided and the tree already has a root node, unless the tree is a forest.
+    /// - The parent node is not found in the tree.
+    /// - A node with the same id is already present in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
+    /// # use tree_ds::prelude::{Tree, Node};
     ///
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(2)), None);
     ///
-    /// let node = Node::new(1, Some(2));
-    /// let node_id = tree.add_node(node.clone(), None).unwrap();
-    ///
-    /// assert_eq!(tree.get_node_by_id(&node_id), Some(node));
+    /// assert!(node_id.is_ok());
+    /// // This should return an error because the tree already has a root node.
+    /// let another_node_id = tree.add_node(Node::new(2, Some(3)), None);
+    /// assert!(another_node_id.is_err());
+    /// // This should return an error because the parent node does not exist.
+    /// let orphan_node_id = tree.add_node(Node::new(3, Some(4)), Some(&5));
+    /// assert!(orphan_node_id.is_err());
     /// ```
-    pub fn get_node_by_id(&self, node_id: &Q) -> Option<Node<Q, T>> {
-        self.nodes
-            .iter()
-            .find(|n| &n.get_node_id() == node_id)
-            .cloned()
+    pub fn add_node(&mut self, node: Node<Q, T>, parent_id: Option<&Q>) -> Result<Q> {
+        let node_id = node.get_node_id();
+        if self.arena.contains(&node_id) {
+            return Err(DuplicateNodeId(node_id.to_string()));
+        }
+        let parent = match parent_id {
+            Some(parent_id) => Some(self.find_handle(parent_id)?),
+            None if !self.forest && self.root_handles().next().is_some() => {
+                return Err(RootNodeAlreadyPresent);
+            }
+            None => None,
+        };
+        self.check_value_indexes(slice::from_ref(&node))?;
+        self.place_node(node, parent, None);
+        Ok(node_id)
     }
 
-    /// Get the root node of the tree.
-    ///
-    /// This method gets the root node of the tree. The root node is the topmost node in the tree. The
-    /// root node has no parent node.
-    ///
-    /// # Returns
-    ///
-    /// The root node of the tree or `None` if the tree has no root node.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
-    ///
-    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node = Node::new(1, Some(2));
-    /// tree.add_node(node.clone(), None).unwrap();
+    /// Store a new node in the tree as a child of the given parent.
     ///
-    /// assert_eq!(tree.get_root_node(), Some(node));
-    /// ```
-    pub fn get_root_node(&self) -> Option<Node<Q, T>> {
-        self.nodes
-            .iter()
-            .find(|n| n.get_parent_id().is_none())
-            .cloned()
+    /// This method stores the node and links it at the given position among the children of the
+    /// parent, or after the last child if no position is given. Links the node had before are dropped.
+    #[doc(hidden)]
+    fn place_node(
+        &mut self,
+        node: Node<Q, T>,
+        parent: Option<NodeHandle>,
+        index: Option<usize>,
+    ) -> NodeHandle {
+        self.track_node(&node);
+        let handle = self.arena.insert(node);
+        // Write the links of the arena, which has none yet, back to the node.
+        self.arena.detach(handle);
+        if let Some(parent) = parent {
+            self.arena.attach(handle, parent, index);
+        }
+        handle
     }
 
-    /// Get the height of the node.
+    /// Insert a node into the tree at a given position among the children of its parent.
     ///
-    /// This method gets the height of the node. The height of the node is the number of edges present
-    /// in the longest path connecting the node to a leaf node.
+    /// This method adds a node to the tree as a child of the parent node with the given parent id. The
+    /// node is inserted at the given index among the children of the parent, shifting the children
+    /// after it to the right.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node to insert.
+    /// * `parent_id` - The id of the parent node.
+    /// * `index` - The index of the node among the children of the parent.
     ///
     /// # Returns
     ///
-    /// The height of the node. If the node is a leaf node, the height is 0.  This method returns an
-    /// error if the node is not found in the tree.
+    /// The id of the node that was inserted into the tree. An error is returned if:
+    /// - The parent node is not found in the tree.
+    /// - A node with the same id is already present in the tree.
+    /// - The index is greater than the number of children of the parent.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use tree_ds::prelude::{Node, Tree};
     ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    ///
-    /// assert!(tree.get_node_height(&node_2).is_ok());
-    /// assert_eq!(tree.get_node_height(&node_2).unwrap(), 1);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.insert_node_at(Node::new(3, Some(6)), &node_1, 0)?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_3, node_2]);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn get_node_height(&self, node_id: &Q) -> Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        let children = node.get_children_ids();
-        if children.is_empty() {
-            return Ok(0);
+    pub fn insert_node_at(&mut self, node: Node<Q, T>, parent_id: &Q, index: usize) -> Result<Q> {
+        let node_id = node.get_node_id();
+        if self.arena.contains(&node_id) {
+            return Err(DuplicateNodeId(node_id.to_string()));
         }
-        let mut height = 0;
-        for child in children {
-            let child_height = self.get_node_height(&child)?;
-            if child_height > height {
-                height = child_height;
-            }
+        let parent = self.find_handle(parent_id)?;
+        let children_count = self.arena.children_count(parent).unwrap_or_default();
+        if index > children_count {
+            return Err(InvalidOperation(format!(
+                "Cannot insert node {} at position {}, node {} has {} children.",
+                node_id, index, parent_id, children_count
+            )));
         }
-        Ok(height + 1)
+        self.check_value_indexes(slice::from_ref(&node))?;
+        self.place_node(node, Some(parent), Some(index));
+        Ok(node_id)
     }
 
-    /// Get the depth of a node in the tree.
+    /// Insert a node into the tree right before one of its siblings.
     ///
-    /// This method gets the depth of a node in the tree. The depth of a node is the length of the path
-    /// from the root node to the node. The depth of the node is the number of edges on the path from the
-    /// root node to the node.
+    /// This method adds a node to the tree as a child of the parent of the sibling with the given id.
+    /// The node is placed right before the sibling among the children of the parent.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node.
+    /// * `node` - The node to insert.
+    /// * `sibling_id` - The id of the sibling to insert the node before.
     ///
     /// # Returns
     ///
-    /// The depth of the node in the tree.  This method returns an error if the node is not found in the tree.
+    /// The id of the node that was inserted into the tree. An error is returned if the sibling is not
+    /// found in the tree, if the sibling is the root node or if a node with the same id is already
+    /// present in the tree.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use tree_ds::prelude::{Node, Tree};
     ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    /// let depth_result = tree.get_node_depth(&node_3);
-    /// assert!(depth_result.is_ok());
-    /// assert_eq!(depth_result.unwrap(), 2);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.insert_before(Node::new(3, Some(6)), &node_2)?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_3, node_2]);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn get_node_depth(&self, node_id: &Q) -> Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        let mut depth = 0;
-        let mut parent = node.get_parent_id();
-        while let Some(parent_id) = parent {
-            depth += 1;
-            parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?
-                .get_parent_id();
-        }
-        Ok(depth)
+    pub fn insert_before(&mut self, node: Node<Q, T>, sibling_id: &Q) -> Result<Q> {
+        let (parent, index) = self.get_sibling_position(sibling_id)?;
+        let parent_id = self
+            .id_of(parent)
+            .ok_or(NodeNotFound(sibling_id.to_string()))?;
+        self.insert_node_at(node, &parent_id, index)
     }
 
-    /// Get the ancestors of a node in the tree.
+    /// Insert a node into the tree right after one of its siblings.
     ///
-    /// This method gets the ancestors of a node in the tree. The ancestors of a node are all the nodes
-    /// that are on the path from the root node to the node, not including the node itself.
+    /// This method adds a node to the tree as a child of the parent of the sibling with the given id.
+    /// The node is placed right after the sibling among the children of the parent.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node.
+    /// * `node` - The node to insert.
+    /// * `sibling_id` - The id of the sibling to insert the node after.
     ///
     /// # Returns
     ///
-    /// The ancestors of the node from closest to furthest.  This method returns an error if the node is not found in the tree.
+    /// The id of the node that was inserted into the tree. An error is returned if the sibling is not
+    /// found in the tree, if the sibling is the root node or if a node with the same id is already
+    /// present in the tree.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use tree_ds::prelude::{Node, Tree};
     ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
-    /// let depth_result = tree.get_ancestor_ids(&node_3);
-    /// assert!(depth_result.is_ok());
-    /// assert_eq!(depth_result.unwrap(), vec![2, 1]);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.insert_after(Node::new(4, Some(5)), &node_2)?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_2, node_4, node_3]);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn get_ancestor_ids(&self, node_id: &Q) -> Result<Vec<Q>> {
-        let node = self
-            .get_node_by_id(node_id)
+    pub fn insert_after(&mut self, node: Node<Q, T>, sibling_id: &Q) -> Result<Q> {
+        let (parent, index) = self.get_sibling_position(sibling_id)?;
+        let parent_id = self
+            .id_of(parent)
+            .ok_or(NodeNotFound(sibling_id.to_string()))?;
+        self.insert_node_at(node, &parent_id, index + 1)
+    }
+
+    /// Get the parent handle of a node and the position of the node among the children of the parent.
+    #[doc(hidden)]
+    fn get_sibling_position(&self, node_id: &Q) -> Result<(NodeHandle, usize)> {
+        let node = self.find_handle(node_id)?;
+        let parent = self
+            .arena
+            .parent(node)
+            .ok_or(InvalidOperation(String::from(
+                "The root node has no siblings.",
+            )))?;
+        let index = self
+            .arena
+            .children(parent)
+            .and_then(|mut children| children.position(|child| child == node))
             .ok_or(NodeNotFound(node_id.to_string()))?;
-        let mut ancestors = vec![];
-        let mut parent = node.get_parent_id();
-        while let Some(parent_id) = parent {
-            ancestors.push(parent_id.clone());
-            parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?
-                .get_parent_id();
-        }
-        Ok(ancestors)
+        Ok((parent, index))
     }
 
-    /// Get the height of the tree.
+    /// Swap the positions of two siblings.
     ///
-    /// This method gets the height of the tree. The height of the tree is the length of the longest path
-    /// from the root node to a leaf node. The height of the tree is the number of edges on the longest
-    /// path from the root node to a leaf node.
+    /// This method swaps the positions of two nodes that share the same parent among the children of
+    /// the parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the first sibling.
+    /// * `second_id` - The id of the second sibling.
     ///
     /// # Returns
     ///
-    /// The height of the tree. This method returns an error if the tree has no root node.
+    /// An error is returned if either node is not found in the tree or if the nodes do not share the
+    /// same parent.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, Result};
+    /// # use tree_ds::prelude::{Node, Tree};
     ///
-    /// # fn main() -> Result<()> {
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
     /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    /// let tree_height = tree.get_height();
-    /// assert!(tree_height.is_ok());
-    /// assert_eq!(tree_height?, 2);
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.swap_siblings(&node_2, &node_3)?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_3, node_2]);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_height(&self) -> Result<i32> {
-        let root = self
-            .get_root_node()
-            .ok_or(InvalidOperation(String::from("Tree has no root node")))?;
-        self.get_node_height(&root.get_node_id())
+    pub fn swap_siblings(&mut self, first_id: &Q, second_id: &Q) -> Result<()> {
+        let (parent, first_index) = self.get_sibling_position(first_id)?;
+        let (second_parent, second_index) = self.get_sibling_position(second_id)?;
+        if parent != second_parent {
+            return Err(InvalidOperation(format!(
+                "Nodes {} and {} are not siblings.",
+                first_id, second_id
+            )));
+        }
+        let mut children = self
+            .arena
+            .children(parent)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        children.swap(first_index, second_index);
+        self.arena.reorder_children(parent, &children);
+        Ok(())
     }
 
-    /// Get the degree of a node in the tree.
+    /// Sort the children of a node.
     ///
-    /// This method gets the degree of a node in the tree. The degree of a node is the number of children
-    /// that the node has.
+    /// This method sorts the children of the node with the given id using the given comparison
+    /// function. The sort is stable, so children that compare equal keep their relative order.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node.
+    /// * `node_id` - The id of the node whose children to sort.
+    /// * `compare` - The function used to compare two children.
     ///
     /// # Returns
     ///
-    /// The degree of the node in the tree. This method returns an error if the node is not found in the tree.
+    /// An error is returned if the node is not found in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Result, Node, Tree};
+    /// # use tree_ds::prelude::{Node, Tree};
     ///
-    /// # fn main() -> Result<()> {
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(9)), Some(&node_1))?;
     /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
-    ///
-    /// assert_eq!(tree.get_node_degree(&node_1)?, 2);
-    /// assert_eq!(tree.get_node_degree(&node_2)?, 0);
-    /// assert_eq!(tree.get_node_degree(&node_3)?, 0);
+    /// tree.sort_children_by(&node_1, |a, b| a.get_value().cmp(&b.get_value()))?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_3, node_2]);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_node_degree(&self, node_id: &Q) -> Result<i32> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        Ok(node.get_children_ids().len() as i32)
+    pub fn sort_children_by<F>(&mut self, node_id: &Q, mut compare: F) -> Result<()>
+    where
+        F: FnMut(&Node<Q, T>, &Node<Q, T>) -> Ordering,
+    {
+        let node = self.find_handle(node_id)?;
+        self.sort_node_children(node, &mut compare);
+        Ok(())
     }
 
-    /// Get the nodes in the tree.
+    /// Sort the children of a node and of all its descendants.
     ///
-    /// This method gets the nodes in the tree.
+    /// This method sorts the children of the node with the given id and the children of every one of
+    /// its descendants using the given comparison function. The sort is stable, so children that
+    /// compare equal keep their relative order.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start sorting from.
+    /// * `compare` - The function used to compare two children.
     ///
     /// # Returns
     ///
-    /// The nodes in the tree.
+    /// An error is returned if the node is not found in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
+    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
     ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
-    /// let node = Node::new(1, Some(2));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(4, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(6, Some(5)), Some(&node_2))?;
+    /// tree.add_node(Node::new(5, Some(7)), Some(&node_2))?;
+    /// tree.sort_children_recursive(&node_1, |a, b| a.get_node_id().cmp(&b.get_node_id()))?;
+    /// assert_eq!(tree.traverse(TraversalStrategy::PreOrder, &node_1)?, vec![1, 3, 4, 5, 6]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sort_children_recursive<F>(&mut self, node_id: &Q, mut compare: F) -> Result<()>
+    where
+        F: FnMut(&Node<Q, T>, &Node<Q, T>) -> Ordering,
+    {
+        let node = self.find_handle(node_id)?;
+        let handles = PreOrderIter::new(self, node)
+            .handles()
+            .map(|(_, handle)| handle)
+            .collect::<Vec<_>>();
+        for handle in handles {
+            self.sort_node_children(handle, &mut compare);
+        }
+        Ok(())
+    }
+
+    /// Sort the children of a node using the given comparison function.
+    #[doc(hidden)]
+    fn sort_node_children<F>(&mut self, node: NodeHandle, compare: &mut F)
+    where
+        F: FnMut(&Node<Q, T>, &Node<Q, T>) -> Ordering,
+    {
+        let mut children = self
+            .arena
+            .children(node)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let arena = &self.arena;
+        children.sort_by(|a, b| match (arena.get(*a), arena.get(*b)) {
+            (Some(a), Some(b)) => compare(a, b),
+            _ => Ordering::Equal,
+        });
+        self.arena.reorder_children(node, &children);
+    }
+
+    /// Get the name of the tree.
+    ///
+    /// This method gets the name of the tree.
+    ///
+    /// # Returns
+    ///
+    /// The name of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// let tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
+    /// ```
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Set the name of the tree.
+    ///
+    /// This method sets the name of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.rename(Some("New Name"));
+    /// assert_eq!(tree.get_name(), Some("New Name"));
+    /// ```
+    pub fn rename(&mut self, name: Option<&str>) {
+        self.name = name.map(|x| x.to_string());
+    }
+
+    /// Get a node in the tree.
+    ///
+    /// This method gets the node with the given node id in the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The node with the given node id in the tree or `None` if the node is not found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// let node_id = tree.add_node(node.clone(), None).unwrap();
+    ///
+    /// assert_eq!(tree.get_node_by_id(&node_id), Some(node));
+    /// ```
+    pub fn get_node_by_id(&self, node_id: &Q) -> Option<Node<Q, T>> {
+        self.arena
+            .get_handle(node_id)
+            .and_then(|handle| self.arena.get(handle))
+            .cloned()
+    }
+
+    /// Get the handle of a node in the tree.
+    ///
+    /// This method gets the handle of the node with the given node id. A handle resolves a node
+    /// without hashing or comparing ids, which makes it suitable for hot paths that visit the same
+    /// nodes repeatedly. The handle stops resolving once the node is removed from the tree, and it never
+    /// resolves in another tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the node or `None` if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, NodeRemovalStrategy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let handle = tree.get_node_handle(&node_2).unwrap();
+    /// tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+    /// assert!(tree.get_node_by_handle(handle).is_none());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_handle(&self, node_id: &Q) -> Option<NodeHandle> {
+        self.arena.get_handle(node_id)
+    }
+
+    /// Get a node in the tree by its handle.
+    ///
+    /// This method gets the node that the given handle points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle of the node.
+    ///
+    /// # Returns
+    ///
+    /// The node or `None` if the node has been removed from the tree or the handle belongs to another
+    /// tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
+    ///
+    /// let handle = tree.get_node_handle(&node_id).unwrap();
+    /// assert_eq!(tree.get_node_by_handle(handle).unwrap().get_node_id(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_by_handle(&self, handle: NodeHandle) -> Option<&Node<Q, T>> {
+        self.arena.get(handle)
+    }
+
+    /// Get the handle of the parent of a node.
+    ///
+    /// This method gets the handle of the parent of the node that the given handle points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle of the node.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the parent or `None` if the node is a root node, has been removed from the tree or
+    /// the handle belongs to another tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let handle = tree.get_node_handle(&node_2).unwrap();
+    /// assert_eq!(tree.get_parent_handle(handle), tree.get_node_handle(&node_1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_parent_handle(&self, handle: NodeHandle) -> Option<NodeHandle> {
+        self.arena.parent(handle)
+    }
+
+    /// Get the handles of the children of a node.
+    ///
+    /// This method gets the handles of the children of the node that the given handle points to, in
+    /// the same order as the children ids of the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle of the node.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the handles of the children or `None` if the node has been removed from the tree
+    /// or the handle belongs to another tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+    ///
+    /// let handle = tree.get_node_handle(&node_1).unwrap();
+    /// let children = tree.get_children_handles(handle).unwrap().collect::<Vec<_>>();
+    /// assert_eq!(tree.get_node_by_handle(children[1]).unwrap().get_node_id(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_children_handles(&self, handle: NodeHandle) -> Option<ChildHandles<'_>> {
+        self.arena.children(handle)
+    }
+
+    /// Get the root node of the tree.
+    ///
+    /// This method gets the root node of the tree. The root node is the topmost node in the tree. The
+    /// root node has no parent node.
+    ///
+    /// # Returns
+    ///
+    /// The root node of the tree or `None` if the tree has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
     /// tree.add_node(node.clone(), None).unwrap();
     ///
-    /// assert_eq!(tree.get_nodes().len(), 1);
+    /// assert_eq!(tree.get_root_node(), Some(node));
+    /// ```
+    pub fn get_root_node(&self) -> Option<Node<Q, T>> {
+        self.root_handles()
+            .next()
+            .and_then(|handle| self.arena.get(handle))
+            .cloned()
+    }
+
+    /// Get the root nodes of the tree.
+    ///
+    /// This method gets all the nodes of the tree that have no parent, in the order they were added to
+    /// the tree. A tree has at most one root node while a forest can have many.
+    ///
+    /// # Returns
+    ///
+    /// The root nodes of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut forest: Tree<i32, i32> = Tree::new_forest(Some("Sample Forest"));
+    /// let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+    /// forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = forest.add_node(Node::new(3, Some(6)), None)?;
+    /// let roots = forest.get_root_nodes().iter().map(|node| node.get_node_id()).collect::<Vec<_>>();
+    /// assert_eq!(roots, vec![node_1, node_3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_root_nodes(&self) -> Vec<Node<Q, T>> {
+        self.root_handles()
+            .filter_map(|handle| self.arena.get(handle))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the handles of the root nodes of the tree, in the order the nodes were added to the tree.
+    #[doc(hidden)]
+    fn root_handles(&self) -> impl Iterator<Item = NodeHandle> + '_ {
+        self.arena
+            .handles()
+            .filter(|handle| self.arena.parent(*handle).is_none())
+    }
+
+    /// Get the height of the node.
+    ///
+    /// This method gets the height of the node. The height of the node is the number of edges present
+    /// in the longest path connecting the node to a leaf node.
+    ///
+    /// # Returns
+    ///
+    /// The height of the node. If the node is a leaf node, the height is 0.  This method returns an
+    /// error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    ///
+    /// assert!(tree.get_node_height(&node_2).is_ok());
+    /// assert_eq!(tree.get_node_height(&node_2).unwrap(), 1);
+    /// ```
+    pub fn get_node_height(&self, node_id: &Q) -> Result<i32> {
+        let node = self.find_handle(node_id)?;
+        let height = PreOrderIter::new(self, node)
+            .handles()
+            .map(|(depth, _)| depth)
+            .max()
+            .unwrap_or_default();
+        Ok(height as i32)
+    }
+
+    /// Get the depth of a node in the tree.
+    ///
+    /// This method gets the depth of a node in the tree. The depth of a node is the length of the path
+    /// from the root node to the node. The depth of the node is the number of edges on the path from the
+    /// root node to the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The depth of the node in the tree.  This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    /// let depth_result = tree.get_node_depth(&node_3);
+    /// assert!(depth_result.is_ok());
+    /// assert_eq!(depth_result.unwrap(), 2);
+    /// ```
+    pub fn get_node_depth(&self, node_id: &Q) -> Result<i32> {
+        let node = self.find_handle(node_id)?;
+        Ok(self.ancestor_handles(node).count() as i32)
+    }
+
+    /// Get the ancestors of a node in the tree.
+    ///
+    /// This method gets the ancestors of a node in the tree. The ancestors of a node are all the nodes
+    /// that are on the path from the root node to the node, not including the node itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The ancestors of the node from closest to furthest.  This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+    /// let depth_result = tree.get_ancestor_ids(&node_3);
+    /// assert!(depth_result.is_ok());
+    /// assert_eq!(depth_result.unwrap(), vec![2, 1]);
+    /// ```
+    pub fn get_ancestor_ids(&self, node_id: &Q) -> Result<Vec<Q>> {
+        let node = self.find_handle(node_id)?;
+        Ok(self
+            .ancestor_handles(node)
+            .filter_map(|handle| self.id_of(handle))
+            .collect())
+    }
+
+    /// Get the handles of the ancestors of a node, from closest to furthest.
+    #[doc(hidden)]
+    fn ancestor_handles(&self, node: NodeHandle) -> impl Iterator<Item = NodeHandle> + '_ {
+        core::iter::successors(self.arena.parent(node), |handle| self.arena.parent(*handle))
+    }
+
+    /// Get the height of the tree.
+    ///
+    /// This method gets the height of the tree. The height of the tree is the length of the longest path
+    /// from the root node to a leaf node. The height of the tree is the number of edges on the longest
+    /// path from the root node to a leaf node.
+    ///
+    /// # Returns
+    ///
+    /// The height of the tree. This method returns an error if the tree has no root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let tree_height = tree.get_height();
+    /// assert!(tree_height.is_ok());
+    /// assert_eq!(tree_height?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_height(&self) -> Result<i32> {
+        let mut height = None;
+        for root in self.root_handles() {
+            let root_height = PreOrderIter::new(self, root)
+                .handles()
+                .map(|(depth, _)| depth as i32)
+                .max();
+            height = height.max(root_height);
+        }
+        height.ok_or(InvalidOperation(String::from("Tree has no root node")))
+    }
+
+    /// Get the degree of a node in the tree.
+    ///
+    /// This method gets the degree of a node in the tree. The degree of a node is the number of children
+    /// that the node has.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The degree of the node in the tree. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Result, Node, Tree};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    ///
+    /// assert_eq!(tree.get_node_degree(&node_1)?, 2);
+    /// assert_eq!(tree.get_node_degree(&node_2)?, 0);
+    /// assert_eq!(tree.get_node_degree(&node_3)?, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_degree(&self, node_id: &Q) -> Result<i32> {
+        let node = self.find_handle(node_id)?;
+        Ok(self.arena.children_count(node).unwrap_or_default() as i32)
+    }
+
+    /// Get the nodes in the tree.
+    ///
+    /// This method gets the nodes in the tree.
+    ///
+    /// # Returns
+    ///
+    /// The nodes in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node = Node::new(1, Some(2));
+    /// tree.add_node(node.clone(), None).unwrap();
+    ///
+    /// assert_eq!(tree.get_nodes().len(), 1);
+    /// ```
+    pub fn get_nodes(&self) -> &Nodes<Q, T> {
+        self.arena.nodes()
+    }
+
+    /// Remove a node from the tree.
+    ///
+    /// This method removes a node from the tree. The node is removed using the given removal strategy.
+    /// The removal strategy determines how the node and its children are removed from the tree. The
+    /// `RetainChildren` strategy retains the children of the node when the node is removed. The
+    /// `RemoveNodeAndChildren` strategy removes the node and its children when the node is removed.
+    ///
+    /// The removed nodes are handed back as a detached subtree rooted at the removed node, so the
+    /// removal can be undone or the nodes moved elsewhere with [`Tree::add_subtree`]. With the
+    /// `RetainChildren` strategy the subtree only holds the removed node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to remove.
+    /// * `strategy` - The strategy to use when removing the node.
+    ///
+    /// # Returns
+    /// The removed nodes as a subtree. An error is returned if the node is not found in the tree or if
+    /// the node is the root node and the removal strategy is `RetainChildren`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, NodeRemovalStrategy, Result};
+    ///
+    /// # fn main() -> Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+    /// assert_eq!(tree.get_nodes().len(), 2);
+    ///
+    /// // Undo the removal of a whole branch.
+    /// let removed = tree.remove_node(&3, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+    /// assert_eq!(tree.get_nodes().len(), 1);
+    /// tree.add_subtree(&node_1, removed)?;
+    /// assert_eq!(tree.get_nodes().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_node(
+        &mut self,
+        node_id: &Q,
+        strategy: NodeRemovalStrategy,
+    ) -> Result<SubTree<Q, T>> {
+        let node = self.find_handle(node_id)?;
+        let parent = self.arena.parent(node);
+        match strategy {
+            NodeRemovalStrategy::RetainChildren => {
+                if parent.is_none() && !self.forest {
+                    return Err(InvalidOperation(String::from(
+                        "Cannot remove root node with RetainChildren strategy",
+                    )));
+                }
+                self.arena.detach(node);
+                // The children of the removed node become children of its parent, or root nodes if
+                // the removed node was a root node of a forest.
+                let children = self
+                    .arena
+                    .children(node)
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                for child in children {
+                    self.arena.detach(child);
+                    if let Some(parent) = parent {
+                        self.arena.attach(child, parent, None);
+                    }
+                }
+                let removed_nodes = self.discard_nodes(&[node]);
+                Ok(SubTree::from_parts(
+                    Some(node_id.to_string()),
+                    Nodes::new(removed_nodes),
+                ))
+            }
+            NodeRemovalStrategy::RemoveNodeAndChildren => {
+                self.arena.detach(node);
+                // Collect the whole branch once and then prune it from the tree in a single pass.
+                let removed = PreOrderIter::new(self, node)
+                    .handles()
+                    .map(|(_, handle)| handle)
+                    .collect::<Vec<_>>();
+                let removed_nodes = self.discard_nodes(&removed);
+                Ok(SubTree::from_parts(
+                    Some(node_id.to_string()),
+                    Nodes::new(removed_nodes),
+                ))
+            }
+        }
+    }
+
+    /// Move a node, together with its descendants, under a new parent.
+    ///
+    /// This method detaches the node from its current parent and attaches it as a child of the new
+    /// parent at the given position. The descendants of the node move along with it.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to move.
+    /// * `new_parent_id` - The id of the new parent of the node.
+    /// * `position` - The index of the node among the children of the new parent, counted after the
+    ///   node has been detached from its current parent. If `None`, the node is added as the last child.
+    ///
+    /// In a forest, this method also merges a root node, and the tree under it, under a node of
+    /// another tree of the forest.
+    ///
+    /// # Returns
+    ///
+    /// An error is returned if:
+    /// - The node or the new parent is not found in the tree.
+    /// - The new parent is the node itself or one of its descendants, since the move would create a cycle.
+    /// - The position is greater than the number of children of the new parent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    ///
+    /// tree.move_node(&node_4, &node_1, Some(0))?;
+    /// assert_eq!(tree.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_4, node_2, node_3]);
+    /// // A node cannot be moved under its own descendant.
+    /// assert!(tree.move_node(&node_1, &node_2, None).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn move_node(
+        &mut self,
+        node_id: &Q,
+        new_parent_id: &Q,
+        position: Option<usize>,
+    ) -> Result<()> {
+        let node = self.find_handle(node_id)?;
+        let new_parent = self.find_handle(new_parent_id)?;
+        if new_parent == node
+            || self
+                .ancestor_handles(new_parent)
+                .any(|handle| handle == node)
+        {
+            return Err(InvalidOperation(format!(
+                "Cannot move node {} under its own descendant {}.",
+                node_id, new_parent_id
+            )));
+        }
+        let mut children_count = self.arena.children_count(new_parent).unwrap_or_default();
+        if self.arena.parent(node) == Some(new_parent) {
+            children_count -= 1;
+        }
+        let position = position.unwrap_or(children_count);
+        if position > children_count {
+            return Err(InvalidOperation(format!(
+                "Cannot move node {} to position {}, node {} has {} children.",
+                node_id, position, new_parent_id, children_count
+            )));
+        }
+        self.arena.detach(node);
+        self.arena.attach(node, new_parent, Some(position));
+        Ok(())
+    }
+
+    /// Promote a node, together with its descendants, to a new root node of the forest.
+    ///
+    /// This method detaches the node from its parent so that it becomes the root node of its own tree
+    /// in the forest. A root node can be merged back under another node with [`Tree::move_node`].
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to promote.
+    ///
+    /// # Returns
+    ///
+    /// An error is returned if the node is not found in the tree or if the tree is not a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut forest: Tree<i32, i32> = Tree::new_forest(Some("Sample Forest"));
+    /// let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// forest.promote_to_root(&node_2)?;
+    /// assert_eq!(forest.get_root_nodes().len(), 2);
+    ///
+    /// // Merge the new root back under the first one.
+    /// forest.move_node(&node_2, &node_1, None)?;
+    /// assert_eq!(forest.get_root_nodes().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn promote_to_root(&mut self, node_id: &Q) -> Result<()> {
+        let node = self.find_handle(node_id)?;
+        if !self.forest {
+            return Err(InvalidOperation(String::from(
+                "Only a forest can have more than one root node.",
+            )));
+        }
+        self.arena.detach(node);
+        Ok(())
+    }
+
+    /// Get a subsection of the tree.
+    ///
+    /// This method gets a subsection of the tree starting from the node with the given node id. The
+    /// subsection is a list of nodes that are descendants of the node with the given node id upto the
+    /// given number of descendants. If the number of descendants is `None`, all the descendants of the
+    /// node are included in the subsection.
+    ///
+    /// The subsection holds copies of the nodes, so it can be changed without affecting the tree. The
+    /// node with the given node id is the root node of the subsection and the children that are not
+    /// part of the subsection are left out, so the subsection is a valid tree of its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to get the subsection from.
+    /// * `generations` - The number of descendants to include in the subsection. If `None`, all the
+    ///   descendants of the node are included in the subsection.
+    ///
+    /// # Returns
+    ///
+    /// The subsection of the tree starting from the node with the given node id. This method returns
+    /// an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// # let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    ///
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let subsection = tree.get_subtree(&node_2, None)?;
+    /// assert_eq!(subsection.get_nodes().len(), 2);
+    /// assert_eq!(subsection.get_root_node().unwrap().get_node_id(), node_2);
+    /// subsection.get_node_by_id(&node_3).unwrap().set_value(Some(4));
+    /// assert_eq!(tree.get_node_by_id(&node_3).unwrap().get_value(), Some(6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_subtree(&self, node_id: &Q, generations: Option<i32>) -> Result<SubTree<Q, T>> {
+        let max_depth = generations.map(|generations| usize::try_from(generations).unwrap_or(0));
+        // Collect the nodes in pre-order with an explicit stack, so that deep trees neither overflow
+        // the stack nor rebuild a subtree for every node.
+        let mut subsection = Nodes::default();
+        let mut stack = vec![(self.find_handle(node_id)?, 0)];
+        while let Some((handle, depth)) = stack.pop() {
+            let node = self
+                .arena
+                .get(handle)
+                .map(Node::deep_clone)
+                .ok_or(NodeNotFound(node_id.to_string()))?;
+            // The copies only link to the nodes that are part of the subsection.
+            if depth == 0 {
+                node.set_parent_id(None);
+            }
+            if matches!(max_depth, Some(max_depth) if depth >= max_depth) {
+                node.set_children_ids(vec![]);
+            } else {
+                let children = self.arena.children(handle).into_iter().flatten();
+                stack.extend(children.rev().map(|child| (child, depth + 1)));
+            }
+            subsection.push(node);
+        }
+        Ok(SubTree::from_parts(Some(node_id.to_string()), subsection))
+    }
+
+    /// Get the siblings of a node in the tree.
+    ///
+    /// This method gets the siblings of a node in the tree. The siblings of a node are the children
+    /// that share the same parent as the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to get the siblings of.
+    /// * `inclusive` - A flag that indicates whether to include the node in the siblings list.
+    ///
+    /// # Returns
+    ///
+    /// The siblings of the node in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
+    ///
+    /// let siblings = tree.get_sibling_ids(&node_2, false)?;
+    /// assert_eq!(siblings.len(), 2);
+    ///
+    /// let siblings = tree.get_sibling_ids(&node_2, true)?;
+    /// assert_eq!(siblings.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_sibling_ids(&self, node_id: &Q, inclusive: bool) -> Result<Vec<Q>> {
+        let node = self.find_handle(node_id)?;
+        if let Some(parent) = self.arena.parent(node) {
+            let siblings = self.children_ids(parent);
+            if inclusive {
+                Ok(siblings)
+            } else {
+                Ok(siblings.into_iter().filter(|x| x != node_id).collect())
+            }
+        } else if inclusive {
+            // We need to clone this since Q does not implement Copy.
+            Ok(vec![node_id.clone()])
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Get the leaves of the tree.
+    ///
+    /// This method gets the ids of all the nodes in the tree that have no children, in the order the
+    /// nodes were added to the tree.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the leaf nodes of the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(tree.get_leaf_ids(), vec![node_3, node_4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_leaf_ids(&self) -> Vec<Q> {
+        self.arena
+            .handles()
+            .filter(|handle| self.arena.children_count(*handle) == Some(0))
+            .filter_map(|handle| self.id_of(handle))
+            .collect()
+    }
+
+    /// Get the descendants of a node.
+    ///
+    /// This method gets the ids of all the nodes below the node in the tree, not including the node
+    /// itself. The tree is walked in place, without building a subtree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the descendants of the node in pre-order. This method returns an error if the node is
+    /// not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(tree.get_descendant_ids(&node_1)?, vec![node_2, node_4, node_3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_descendant_ids(&self, node_id: &Q) -> Result<Vec<Q>> {
+        Ok(self
+            .iter_pre_order(node_id)?
+            .skip(1)
+            .map(|node| node.get_node_id())
+            .collect())
+    }
+
+    /// Get the size of the subtree under a node.
+    ///
+    /// This method counts the nodes of the subtree rooted at the node, including the node itself. The
+    /// tree is walked in place, without building a subtree.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The number of nodes in the subtree. This method returns an error if the node is not found in the
+    /// tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(tree.get_subtree_size(&node_1)?, 4);
+    /// assert_eq!(tree.get_subtree_size(&node_2)?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_subtree_size(&self, node_id: &Q) -> Result<usize> {
+        Ok(self.iter_pre_order(node_id)?.count())
+    }
+
+    /// Get the nodes at a given depth.
+    ///
+    /// This method gets all the nodes whose depth is the given depth, from left to right. In a forest
+    /// the nodes of every tree are included, one tree after the other.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The depth of the nodes, the root nodes being at depth 0.
+    ///
+    /// # Returns
+    ///
+    /// The nodes at the given depth. The list is empty if no node is that deep.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(tree.get_nodes_at_depth(1).len(), 2);
+    /// assert!(tree.get_nodes_at_depth(3).is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_nodes_at_depth(&self, depth: i32) -> Nodes<Q, T> {
+        let Ok(depth) = usize::try_from(depth) else {
+            return Nodes::default();
+        };
+        let mut nodes = vec![];
+        for root in self.root_handles() {
+            nodes.extend(
+                LevelOrderIter::new(self, root)
+                    .handles()
+                    .skip_while(|(node_depth, _)| *node_depth < depth)
+                    .take_while(|(node_depth, _)| *node_depth == depth)
+                    .filter_map(|(_, handle)| self.arena.get(handle).cloned()),
+            );
+        }
+        Nodes::new(nodes)
+    }
+
+    /// Get the width of the tree.
+    ///
+    /// This method gets the number of nodes at the widest depth of the tree. In a forest the nodes at
+    /// the same depth in every tree are counted together.
+    ///
+    /// # Returns
+    ///
+    /// The width of the tree, which is 0 for an empty tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// tree.add_node(Node::new(5, Some(7)), Some(&node_2))?;
+    /// assert_eq!(tree.get_width(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_width(&self) -> usize {
+        let mut widths: Vec<usize> = vec![];
+        for root in self.root_handles() {
+            for (depth, _) in LevelOrderIter::new(self, root).handles() {
+                if widths.len() <= depth {
+                    widths.push(0);
+                }
+                widths[depth] += 1;
+            }
+        }
+        widths.into_iter().max().unwrap_or(0)
+    }
+
+    /// Check if a node is an ancestor of another node.
+    ///
+    /// This method checks if the first node is on the path from the second node to its root node. A
+    /// node is not an ancestor of itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `ancestor_id` - The id of the possible ancestor.
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the first node is an ancestor of the second node, `false` otherwise. This method
+    /// returns an error if either node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// assert!(tree.is_ancestor_of(&node_1, &node_3)?);
+    /// assert!(!tree.is_ancestor_of(&node_3, &node_1)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_ancestor_of(&self, ancestor_id: &Q, node_id: &Q) -> Result<bool> {
+        let ancestor = self.find_handle(ancestor_id)?;
+        let node = self.find_handle(node_id)?;
+        Ok(self.ancestor_handles(node).any(|handle| handle == ancestor))
+    }
+
+    /// Add a subsection to the tree.
+    ///
+    /// This method adds a subsection to the tree. The subsection is a list of nodes that are descendants
+    /// of the node with the given node id. The subsection is added as children of the node with the
+    /// given node id. The tree stores copies of the nodes of the subsection, so the tree the subsection
+    /// was taken from is not changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to add the subsection to.
+    /// * `subtree` - The subsection to add to the tree.
+    ///
+    /// # Returns
+    /// This function return an error if:
+    /// - The node is not found in the tree.
+    /// - The subsection has no root node or more than one root node.
+    /// - A node in the subsection has the same id as a node that is already in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, SubTree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let mut subtree = SubTree::new(Some("Sample Tree"));
+    /// let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
+    /// subtree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// tree.add_subtree(&node_id, subtree)?;
+    /// assert_eq!(tree.get_nodes().len(), 3);
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn get_nodes(&self) -> &Nodes<Q, T> {
-        self.nodes.as_ref()
+    pub fn add_subtree(&mut self, node_id: &Q, subtree: SubTree<Q, T>) -> Result<()> {
+        let node = self.find_handle(node_id)?;
+        // Get the root node in the subsection and add it as a child of the node.
+        let subtree_nodes = subtree.get_nodes();
+        let mut roots = subtree.root_handles();
+        let root = roots
+            .next()
+            .ok_or(InvalidOperation(String::from("Subtree has no root node.")))?;
+        if roots.next().is_some() {
+            return Err(InvalidOperation(String::from(
+                "Subtree has more than one root node.",
+            )));
+        }
+        if let Some(duplicate) = subtree_nodes
+            .iter()
+            .find(|n| self.arena.contains(&n.get_node_id()))
+        {
+            return Err(DuplicateNodeId(duplicate.get_node_id().to_string()));
+        }
+        self.check_value_indexes(&subtree_nodes.iter().cloned().collect::<Vec<_>>())?;
+        // Copy the nodes in the order they are stored in the subtree, then copy the links between
+        // them from the subtree. The nodes are deep copies, since the caller may still hold the nodes
+        // of the subtree.
+        let mut placed = vec![];
+        let mut handles = IdMap::new();
+        for handle in subtree.arena.handles() {
+            if let Some(subtree_node) = subtree.arena.get(handle) {
+                let new_handle = self.place_node(subtree_node.deep_clone(), None, None);
+                placed.push((handle, new_handle));
+                handles.insert(handle, new_handle);
+            }
+        }
+        for (handle, new_handle) in placed {
+            for child in subtree.arena.children(handle).into_iter().flatten() {
+                self.arena.attach(handles[&child], new_handle, None);
+            }
+        }
+        self.arena.attach(handles[&root], node, None);
+        Ok(())
     }
 
-    /// Remove a node from the tree.
+    /// Traverse the subtree from the given node.
     ///
-    /// This method removes a node from the tree. The node is removed using the given removal strategy.
-    /// The removal strategy determines how the node and its children are removed from the tree. The
-    /// `RetainChildren` strategy retains the children of the node when the node is removed. The
-    /// `RemoveNodeAndChildren` strategy removes the node and its children when the node is removed.
+    /// This method traverses the subtree from the given node in the given order.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node to remove.
-    /// * `strategy` - The strategy to use when removing the node.
+    /// * `order` - The order to traverse the tree.
+    /// * `node_id` - The id of the node to start the traversal from.
     ///
     /// # Returns
-    /// An error if the node is not found in the tree or if the node is the root node and the removal
-    /// strategy is `RetainChildren`.
+    ///
+    /// The nodes in the tree in the given order. This method returns an error if the node is not found
+    /// in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, NodeRemovalStrategy, Result};
+    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
     ///
-    /// # fn main() -> Result<()> {
+    /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    ///
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
     /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
     ///
-    /// tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
-    /// assert_eq!(tree.get_nodes().len(), 2);
+    /// let ordered_nodes = tree.traverse(TraversalStrategy::PreOrder, &node_1)?;
+    /// # let expected = vec![1, 2, 3];
+    /// # assert_eq!(ordered_nodes, expected);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn remove_node(&mut self, node_id: &Q, strategy: NodeRemovalStrategy) -> Result<()> {
-        match strategy {
-            NodeRemovalStrategy::RetainChildren => {
-                let node = self
-                    .get_node_by_id(node_id)
-                    .ok_or(NodeNotFound(node_id.to_string()))?;
-                let parent_node_id = &node.get_parent_id().ok_or(InvalidOperation(
-                    String::from("Cannot remove root node with RetainChildren strategy"),
-                ))?;
-                let parent_node = self
-                    .get_node_by_id(parent_node_id)
-                    .ok_or(NodeNotFound(parent_node_id.to_string()))?;
-                parent_node.remove_child(node.clone());
-                let children = node.get_children_ids();
-                for child in children {
-                    if let Some(child) = self.get_node_by_id(&child) {
-                        parent_node.add_child(child);
-                    }
-                }
-                self.nodes.retain(|n| &n.get_node_id() != node_id);
-                Ok(())
+    pub fn traverse(&self, order: TraversalStrategy, node_id: &Q) -> Result<Vec<Q>> {
+        let node = self.find_handle(node_id)?;
+        let nodes = match &order {
+            TraversalStrategy::PreOrder => PreOrderIter::new(self, node)
+                .handles()
+                .filter_map(|(_, handle)| self.id_of(handle))
+                .collect(),
+            TraversalStrategy::PostOrder => PostOrderIter::new(self, node)
+                .handles()
+                .filter_map(|(_, handle)| self.id_of(handle))
+                .collect(),
+            TraversalStrategy::InOrder => self.in_order_ids(node),
+            TraversalStrategy::LevelOrder => self.traverse_levels(node_id)?.concat(),
+            TraversalStrategy::ReverseLevelOrder => {
+                let mut levels = self.traverse_levels(node_id)?;
+                levels.reverse();
+                levels.concat()
             }
-            NodeRemovalStrategy::RemoveNodeAndChildren => {
-                let node = self
-                    .get_node_by_id(node_id)
-                    .ok_or(NodeNotFound(node_id.to_string()))?;
-                let children = node.get_children_ids();
-                if let Some(parent_id) = node.get_parent_id() {
-                    let parent = self
-                        .get_node_by_id(&parent_id)
-                        .ok_or(NodeNotFound(parent_id.to_string()))?;
-                    parent.remove_child(node.clone());
-                }
-                self.nodes.retain(|n| &n.get_node_id() != node_id);
-                for child in children {
-                    let child = self
-                        .get_node_by_id(&child)
-                        .ok_or(NodeNotFound(child.to_string()))?;
-                    node.remove_child(child.clone());
-                    self.remove_node(&child.get_node_id(), strategy)?;
+            TraversalStrategy::ZigZag => {
+                let mut levels = self.traverse_levels(node_id)?;
+                levels
+                    .iter_mut()
+                    .skip(1)
+                    .step_by(2)
+                    .for_each(|level| level.reverse());
+                levels.concat()
+            }
+        };
+        Ok(nodes)
+    }
+
+    /// Collect the ids of the subtree from the given node in in-order.
+    ///
+    /// The first child of a node is visited before the node, and every other child is visited right
+    /// after the node, ahead of its own subtree. A node without children has nothing to visit. The
+    /// nodes are collected with an explicit stack, so deep trees do not overflow the call stack.
+    fn in_order_ids(&self, node: NodeHandle) -> Vec<Q> {
+        let mut nodes = vec![];
+        // The first flag records whether the node is visited before its children, and the second
+        // one whether its children have already been pushed onto the stack.
+        let mut stack = vec![];
+        if self.arena.children_count(node).unwrap_or(0) > 0 {
+            stack.push((node, false, false));
+        }
+        while let Some((handle, leading, expanded)) = stack.pop() {
+            if leading || expanded {
+                nodes.extend(self.id_of(handle));
+            }
+            if expanded {
+                continue;
+            }
+            let mut children = self.arena.children(handle).into_iter().flatten();
+            let Some(first) = children.next() else {
+                if !leading {
+                    nodes.extend(self.id_of(handle));
                 }
-                Ok(())
+                continue;
+            };
+            stack.extend(children.rev().map(|child| (child, true, false)));
+            if !leading {
+                stack.push((handle, false, true));
             }
+            stack.push((first, false, false));
         }
+        nodes
     }
 
-    /// Get a subsection of the tree.
+    /// Traverse all the trees of a forest.
     ///
-    /// This method gets a subsection of the tree starting from the node with the given node id. The
-    /// subsection is a list of nodes that are descendants of the node with the given node id upto the
-    /// given number of descendants. If the number of descendants is `None`, all the descendants of the
-    /// node are included in the subsection.
+    /// This method traverses the tree under every root node in the given order, one tree after the
+    /// other, starting from the first root node. For a tree with a single root node it is the same as
+    /// traversing from the root node.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node to get the subsection from.
-    /// * `generations` - The number of descendants to include in the subsection. If `None`, all the
-    /// descendants of the node are included in the subsection.
+    /// * `order` - The order to traverse the trees.
     ///
     /// # Returns
     ///
-    /// The subsection of the tree starting from the node with the given node id.
+    /// The ids of the nodes of all the trees in the order they were visited.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree};
+    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
     ///
     /// # fn main() -> tree_ds::prelude::Result<()> {
-    /// # let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let mut forest: Tree<i32, i32> = Tree::new_forest(Some("Sample Forest"));
+    /// let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = forest.add_node(Node::new(2, Some(3)), None)?;
+    /// forest.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// forest.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(forest.traverse_all(TraversalStrategy::PostOrder)?, vec![3, 1, 4, 2]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn traverse_all(&self, order: TraversalStrategy) -> Result<Vec<Q>> {
+        let mut nodes = vec![];
+        for root in self.root_handles() {
+            if let Some(root_id) = self.id_of(root) {
+                nodes.append(&mut self.traverse(order, &root_id)?);
+            }
+        }
+        Ok(nodes)
+    }
+
+    /// Iterate over the subtree from the given node in pre-order.
+    ///
+    /// This method returns a lazy iterator that visits a node before its children. Unlike
+    /// [`Tree::traverse`], the nodes are produced one at a time, so a traversal that stops early does
+    /// not visit the rest of the tree. The iterator does not recurse, so it can walk trees of any depth.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node to start the traversal from.
+    ///
+    /// # Returns
+    ///
+    /// The pre-order iterator. This method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
     ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
     /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
     /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
     ///
-    /// let subsection = tree.get_subtree(&node_2, None)?;
-    /// assert_eq!(subsection.get_nodes().len(), 2);
+    /// let node = tree.iter_pre_order(&node_1)?.find(|node| node.get_value() == Some(3));
+    /// assert_eq!(node.unwrap().get_node_id(), node_2);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_subtree(&self, node_id: &Q, generations: Option<i32>) -> Result<SubTree<Q, T>> {
-        let mut subsection = Nodes::default();
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        subsection.push(node.clone());
-        // Get the subsequent children of the node recursively for the number of generations and add them to the subsection.
-        if let Some(generations) = generations {
-            let children = node.get_children_ids();
-            for current_generation in 0..generations {
-                for child in children.clone() {
-                    subsection.append(
-                        &mut self
-                            .get_subtree(&child, Some(current_generation))?
-                            .get_nodes()
-                            .clone(),
-                    );
-                }
-            }
-        } else {
-            let children = node.get_children_ids();
-            for child in children {
-                subsection.append(&mut self.get_subtree(&child, None)?.get_nodes().clone());
-            }
-        }
-
-        Ok(SubTree {
-            name: Some(node_id.to_string()),
-            nodes: subsection,
-        })
+    pub fn iter_pre_order(&self, node_id: &Q) -> Result<PreOrderIter<'_, Q, T>> {
+        let node = self.find_handle(node_id)?;
+        Ok(PreOrderIter::new(self, node))
     }
 
-    /// Get the siblings of a node in the tree.
+    /// Iterate over the subtree from the given node in post-order.
     ///
-    /// This method gets the siblings of a node in the tree. The siblings of a node are the children
-    /// that share the same parent as the node.
+    /// This method returns a lazy iterator that visits a node after all its children. The iterator
+    /// does not recurse, so it can walk trees of any depth.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node to get the siblings of.
-    /// * `inclusive` - A flag that indicates whether to include the node in the siblings list.
+    /// * `node_id` - The id of the node to start the traversal from.
     ///
     /// # Returns
     ///
-    /// The siblings of the node in the tree.
+    /// The post-order iterator. This method returns an error if the node is not found in the tree.
     ///
     /// # Example
     ///
@@ -643,161 +2033,104 @@ where
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
     /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
-    /// tree.add_node(Node::new(4, Some(7)), Some(&node_1))?;
-    ///
-    /// let siblings = tree.get_sibling_ids(&node_2, false)?;
-    /// assert_eq!(siblings.len(), 2);
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
     ///
-    /// let siblings = tree.get_sibling_ids(&node_2, true)?;
-    /// assert_eq!(siblings.len(), 3);
+    /// let ids = tree
+    ///     .iter_post_order(&node_1)?
+    ///     .map(|node| node.get_node_id())
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(ids, vec![node_2, node_3, node_1]);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn get_sibling_ids(&self, node_id: &Q, inclusive: bool) -> Result<Vec<Q>> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        return if let Some(parent_id) = node.get_parent_id() {
-            let parent = self
-                .get_node_by_id(&parent_id)
-                .ok_or(NodeNotFound(parent_id.to_string()))?;
-            if inclusive {
-                Ok(parent.get_children_ids().clone())
-            } else {
-                Ok(parent
-                    .get_children_ids()
-                    .iter()
-                    .filter(|x| *x != node_id)
-                    .cloned()
-                    .collect())
-            }
-        } else if inclusive {
-            // We need to clone this since Q does not implement Copy.
-            Ok(vec![node_id.clone()])
-        } else {
-            Ok(vec![])
-        };
+    pub fn iter_post_order(&self, node_id: &Q) -> Result<PostOrderIter<'_, Q, T>> {
+        let node = self.find_handle(node_id)?;
+        Ok(PostOrderIter::new(self, node))
     }
 
-    /// Add a subsection to the tree.
+    /// Iterate over the subtree from the given node in level-order.
     ///
-    /// This method adds a subsection to the tree. The subsection is a list of nodes that are descendants
-    /// of the node with the given node id. The subsection is added as children of the node with the
-    /// given node id.
+    /// This method returns a lazy iterator that visits the nodes breadth-first, one level at a time
+    /// and from left to right within a level.
     ///
     /// # Arguments
     ///
-    /// * `node_id` - The id of the node to add the subsection to.
-    /// * `subtree` - The subsection to add to the tree.
+    /// * `node_id` - The id of the node to start the traversal from.
     ///
     /// # Returns
-    /// This function return an error if:
-    /// - The node is not found in the tree.
-    /// - The subsection has no root node.
+    ///
+    /// The level-order iterator. This method returns an error if the node is not found in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, SubTree};
+    /// # use tree_ds::prelude::{Node, Tree, TreeIterator};
     ///
     /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
-    /// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
-    /// let mut subtree = SubTree::new(Some("Sample Tree"));
-    /// let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
-    /// subtree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
-    /// tree.add_subtree(&node_id, subtree)?;
-    /// assert_eq!(tree.get_nodes().len(), 3);
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let shallow = tree
+    ///     .iter_level_order(&node_1)?
+    ///     .with_depth()
+    ///     .take_while(|(depth, _)| *depth < 2)
+    ///     .count();
+    /// assert_eq!(shallow, 2);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn add_subtree(&mut self, node_id: &Q, subtree: SubTree<Q, T>) -> Result<()> {
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        // Get the root node in the subsection and add it as a child of the node.
-        let subtree_nodes = subtree.get_nodes();
-        let root_node = subtree
-            .get_root_node()
-            .ok_or(InvalidOperation(String::from("Subtree has no root node.")))?;
-        node.add_child(root_node.clone());
-        self.nodes.append(&mut subtree_nodes.clone());
-        Ok(())
+    pub fn iter_level_order(&self, node_id: &Q) -> Result<LevelOrderIter<'_, Q, T>> {
+        let node = self.find_handle(node_id)?;
+        Ok(LevelOrderIter::new(self, node))
     }
 
-    /// Traverse the subtree from the given node.
+    /// Traverse the subtree from the given node level by level.
     ///
-    /// This method traverses the subtree from the given node in the given order.
+    /// This method traverses the subtree from the given node in level-order and groups the ids of the
+    /// nodes by their depth relative to the given node. The first level only holds the given node, the
+    /// second level holds its children and so on. The nodes in each level are ordered from left to right.
     ///
     /// # Arguments
     ///
-    /// * `order` - The order to traverse the tree.
     /// * `node_id` - The id of the node to start the traversal from.
     ///
     /// # Returns
     ///
-    /// The nodes in the tree in the given order. This method returns an error if the node is not found
+    /// The ids of the nodes grouped by level. This method returns an error if the node is not found
     /// in the tree.
     ///
     /// # Example
     ///
     /// ```rust
-    /// # use tree_ds::prelude::{Node, Tree, TraversalStrategy};
+    /// # use tree_ds::prelude::{Node, Tree};
     ///
     /// # fn main() -> tree_ds::prelude::Result<()> {
     /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
     /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
     /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
-    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
     ///
-    /// let ordered_nodes = tree.traverse(TraversalStrategy::PreOrder, &node_1)?;
-    /// # let expected = vec![1, 2, 3];
-    /// # assert_eq!(ordered_nodes, expected);
+    /// let levels = tree.traverse_levels(&node_1)?;
+    /// assert_eq!(levels, vec![vec![node_1], vec![node_2, node_3], vec![node_4]]);
     /// # Ok(())
     /// # }
     /// ```
-    pub fn traverse(&self, order: TraversalStrategy, node_id: &Q) -> Result<Vec<Q>> {
-        let mut nodes = vec![];
-        let node = self
-            .get_node_by_id(node_id)
-            .ok_or(NodeNotFound(node_id.to_string()))?;
-        match &order {
-            TraversalStrategy::PreOrder => {
-                nodes.push(node_id.clone());
-                for child_id in node.get_children_ids().iter() {
-                    nodes.append(&mut self.traverse(order, child_id)?);
-                }
-            }
-            TraversalStrategy::PostOrder => {
-                for child_id in node.get_children_ids().iter() {
-                    nodes.append(&mut self.traverse(order, child_id)?);
-                }
-                nodes.push(node_id.clone());
-            }
-            TraversalStrategy::InOrder => {
-                for (index, child_id) in node.get_children_ids().iter().enumerate() {
-                    if index == 0 {
-                        nodes.append(&mut self.traverse(order, child_id)?);
-                        if !nodes.contains(child_id) {
-                            nodes.push(child_id.clone());
-                        }
-                        if !nodes.contains(node_id) {
-                            nodes.push(node_id.clone());
-                        }
-                    } else {
-                        nodes.push(child_id.clone());
-                        nodes.append(&mut self.traverse(order, child_id)?);
-                    }
-                }
+    pub fn traverse_levels(&self, node_id: &Q) -> Result<Vec<Vec<Q>>> {
+        let node = self.find_handle(node_id)?;
+        let mut levels = vec![];
+        let mut level = vec![node];
+        while !level.is_empty() {
+            let mut next_level = vec![];
+            for node in level.iter() {
+                next_level.extend(self.arena.children(*node).into_iter().flatten());
             }
+            levels.push(level.iter().filter_map(|node| self.id_of(*node)).collect());
+            level = next_level;
         }
-        #[cfg(not(feature = "no_std"))]
-        let mut seen = HashSet::new();
-        #[cfg(feature = "no_std")]
-        let mut seen = BTreeSet::new();
-        nodes.retain(|x| seen.insert(x.clone()));
-        Ok(nodes)
+        Ok(levels)
     }
 
     /// Print the tree.
@@ -807,7 +2140,7 @@ where
     fn print_tree(
         tree: &Tree<Q, T>,
         f: &mut Formatter<'_>,
-        node: &Node<Q, T>,
+        handle: NodeHandle,
         level: usize,
         mut is_within: (bool, usize),
         is_last_child: bool,
@@ -816,6 +2149,9 @@ where
         Q: PartialEq + Eq + Clone + Display + Hash,
         T: PartialEq + Eq + Clone + Display + Default,
     {
+        let node = tree.arena.get(handle).ok_or(InvalidOperation(String::from(
+            "The node is not in the tree.",
+        )))?;
         for x in 1..level {
             if is_within.0 && x == is_within.1 {
                 write!(f, "│   ")?;
@@ -829,64 +2165,167 @@ where
             } else {
                 writeln!(f, "├── {}", node)?;
             }
-        } else {
-            writeln!(f, "{}", node)?;
+        } else {
+            writeln!(f, "{}", node)?;
+        }
+        let children_count = tree.arena.children_count(handle).unwrap_or_default();
+        for (index, child) in tree
+            .arena
+            .children(handle)
+            .into_iter()
+            .flatten()
+            .enumerate()
+        {
+            let last_item = index == children_count - 1;
+            // Check if parent was last child
+            let is_parent_last_item = match tree.arena.parent(handle) {
+                Some(parent) => {
+                    tree.arena.children(parent).and_then(|mut c| c.next_back()) == Some(handle)
+                }
+                None => true,
+            };
+            if !is_within.0 {
+                is_within.0 = !is_parent_last_item;
+                is_within.1 = level;
+            } else {
+                is_within.1 = if level > 1 && level <= 3 {
+                    level - 1
+                } else if level > 3 {
+                    level - 2
+                } else {
+                    level
+                };
+            }
+            Tree::print_tree(
+                tree,
+                f,
+                child,
+                level + 1,
+                (is_within.0, is_within.1),
+                last_item,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<Q, T> Default for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create a new tree with no nodes.
+    fn default() -> Self {
+        Tree {
+            name: None,
+            forest: false,
+            arena: Arena::default(),
+            value_indexes: Watchers::default(),
+            #[cfg(feature = "auto_id")]
+            id_generator: None,
+        }
+    }
+}
+
+impl<Q, T> PartialEq for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Compare two trees for equality.
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.arena.nodes() == other.arena.nodes()
+            && self.forest == other.forest
+    }
+}
+
+impl<Q, T> Eq for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+}
+
+impl<Q, T> Hash for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Hash,
+    T: PartialEq + Eq + Clone + Hash,
+{
+    /// Hash the tree.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.arena.nodes().hash(state);
+        self.forest.hash(state);
+    }
+}
+
+impl<Q, T> Debug for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Debug,
+    T: PartialEq + Eq + Clone + Debug,
+{
+    /// Format the tree for debugging. The links are left out since the nodes hold their ids.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Tree")
+            .field("name", &self.name)
+            .field("nodes", self.arena.nodes())
+            .field("forest", &self.forest)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, Q, T> Deserialize<'de> for Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Deserialize<'de>,
+    T: PartialEq + Eq + Clone + Deserialize<'de>,
+{
+    /// Deserialize the tree and rebuild its lookup table.
+    ///
+    /// The deserialized tree is checked with [`Tree::validate`], and an error listing the violations
+    /// is returned if it is not a valid tree. Use [`Tree::from_unchecked_parts`] to skip the checks.
+    fn deserialize<D>(deserializer: D) -> crate::lib::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let data: TreeData<Q, T> = Deserialize::deserialize(deserializer)?;
+        let mut tree = Tree::from_parts(data.name, data.nodes);
+        tree.forest = data.forest;
+        let violations = tree.validate();
+        if !violations.is_empty() {
+            let mut message = String::from("Invalid tree:");
+            for violation in violations.iter() {
+                write!(message, " {}", violation).map_err(serde::de::Error::custom)?;
+            }
+            return Err(serde::de::Error::custom(message));
         }
-        let children = node.get_children_ids();
-        let children_count = children.len();
-        for (index, child) in children.iter().enumerate() {
-            let child = tree
-                .get_node_by_id(child)
-                .ok_or(NodeNotFound(child.to_string()))?;
-            let last_item = index == children_count - 1;
-            // Check if parent was last child
-            let is_parent_last_item = if let Some(parent) = node.get_parent_id() {
-                let parent = tree
-                    .get_node_by_id(&parent)
-                    .ok_or(NodeNotFound(parent.to_string()))?;
-                let children_ids = parent.get_children_ids();
-                let last_child = children_ids.last();
-                last_child.is_some() && last_child.unwrap() == &node.get_node_id()
-            } else {
-                true
-            };
-            if !is_within.0 {
-                is_within.0 = !is_parent_last_item;
-                is_within.1 = level;
-            } else {
-                is_within.1 = if level > 1 && level <= 3 {
-                    level - 1
-                } else if level > 3 {
-                    level - 2
-                } else {
-                    level
-                };
-            }
-            Tree::print_tree(
-                tree,
-                f,
-                &child,
-                level + 1,
-                (is_within.0, is_within.1),
-                last_item,
-            )?;
+        #[cfg(feature = "auto_id")]
+        {
+            tree.id_generator = data.id_generator.map(TreeIdGenerator::Builtin);
         }
-        Ok(())
+        Ok(tree)
     }
 }
 
-impl<Q, T> Default for Tree<Q, T>
+/// The serialized form of a tree.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[derive(Deserialize)]
+#[serde(rename = "Tree")]
+struct TreeData<Q, T>
 where
     Q: PartialEq + Eq + Clone,
     T: PartialEq + Eq + Clone,
 {
-    /// Create a new tree with no nodes.
-    fn default() -> Self {
-        Tree {
-            name: None,
-            nodes: Nodes::default(),
-        }
-    }
+    #[serde(default)]
+    name: Option<String>,
+    nodes: Nodes<Q, T>,
+    #[serde(default)]
+    forest: bool,
+    #[cfg(feature = "auto_id")]
+    #[serde(default)]
+    id_generator: Option<BuiltinIdGenerator>,
 }
 
 impl<Q, T> Display for Tree<Q, T>
@@ -904,8 +2343,13 @@ where
                 name.clone().chars().map(|_| "*").collect::<String>()
             )?;
         }
-        let node = self.get_root_node().ok_or(FmtError)?;
-        Tree::print_tree(self, f, &node, 0, (false, 0), true).map_err(|_| FmtError)?;
+        let roots = self.root_handles().collect::<Vec<_>>();
+        if roots.is_empty() {
+            return Err(FmtError);
+        }
+        for root in roots {
+            Tree::print_tree(self, f, root, 0, (false, 0), true).map_err(|_| FmtError)?;
+        }
         Ok(())
     }
 }
@@ -918,7 +2362,10 @@ where
     /// Drop the tree.
     #[doc(hidden)]
     fn drop(&mut self) {
-        self.nodes.clear();
+        for node in self.arena.nodes().iter() {
+            self.untrack_node(node);
+        }
+        self.arena.clear();
     }
 }
 
@@ -935,25 +2382,168 @@ mod tests {
 
     use super::*;
 
+    /// Build the tree shared by the tests of the tree modules.
+    ///
+    /// Node 1 is the root with children 2 and 3, node 2 has children 4 and 5, and node 3 has child 6.
+    pub(crate) fn sample_tree() -> crate::prelude::Result<Tree<i32, i32>> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        tree.add_node(Node::new(5, Some(6)), Some(&node_2))?;
+        tree.add_node(Node::new(6, Some(7)), Some(&node_3))?;
+        Ok(tree)
+    }
+
     #[test]
     fn test_tree_new() {
         let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
-        assert_eq!(tree.nodes.len(), 0);
+        assert_eq!(tree.get_nodes().len(), 0);
     }
 
     #[test]
     fn test_tree_add_node() {
         let mut tree = Tree::new(Some("Sample Tree"));
         let node_id = tree.add_node(Node::new(1, Some(2)), None).unwrap();
-        assert_eq!(tree.nodes.len(), 1);
+        assert_eq!(tree.get_nodes().len(), 1);
         assert_eq!(node_id, 1);
         let node_id_2 = tree.add_node(Node::new(2, Some(3)), Some(&1)).unwrap();
-        assert_eq!(tree.nodes.len(), 2);
+        assert_eq!(tree.get_nodes().len(), 2);
         assert_eq!(node_id_2, 2);
         let node_2 = tree.get_node_by_id(&2).unwrap();
         assert_eq!(node_2.get_parent_id().unwrap(), 1);
     }
 
+    #[test]
+    fn test_tree_insert_node_at() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.insert_node_at(Node::new(3, Some(6)), &node_1, 0)?;
+        let node_4 = tree.insert_node_at(Node::new(4, Some(5)), &node_1, 2)?;
+        let node_5 = tree.insert_before(Node::new(5, Some(7)), &node_2)?;
+        let node_6 = tree.insert_after(Node::new(6, Some(8)), &node_2)?;
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![node_3, node_5, node_2, node_6, node_4]
+        );
+        let handles = tree
+            .get_children_handles(tree.get_node_handle(&node_1).unwrap())
+            .unwrap()
+            .map(|handle| tree.get_node_by_handle(handle).unwrap().get_node_id())
+            .collect::<Vec<_>>();
+        assert_eq!(handles, vec![node_3, node_5, node_2, node_6, node_4]);
+
+        assert!(tree
+            .insert_node_at(Node::new(7, Some(9)), &node_1, 6)
+            .is_err());
+        assert!(tree.insert_node_at(Node::new(7, Some(9)), &8, 0).is_err());
+        assert!(tree.insert_before(Node::new(7, Some(9)), &node_1).is_err());
+        assert!(tree.insert_after(Node::new(2, Some(9)), &node_3).is_err());
+        assert_eq!(tree.get_nodes().len(), 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_swap_siblings() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+        let node_5 = tree.add_node(Node::new(5, Some(7)), Some(&node_2))?;
+        tree.swap_siblings(&node_4, &node_2)?;
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![node_4, node_3, node_2]
+        );
+        assert!(matches!(
+            tree.swap_siblings(&node_5, &node_3),
+            Err(InvalidOperation(_))
+        ));
+        assert!(matches!(
+            tree.swap_siblings(&node_1, &node_3),
+            Err(InvalidOperation(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_sort_children() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(1)), Some(&node_1))?;
+        tree.add_node(Node::new(4, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(5, Some(9)), Some(&node_2))?;
+        tree.add_node(Node::new(6, Some(8)), Some(&node_2))?;
+
+        tree.sort_children_by(&node_1, |a, b| b.get_value().cmp(&a.get_value()))?;
+        tree.debug_assert_valid();
+        // The sort is stable, so node 2 stays before node 4.
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![2, 4, 3]
+        );
+        assert_eq!(
+            tree.get_node_by_id(&node_2).unwrap().get_children_ids(),
+            vec![5, 6]
+        );
+
+        tree.sort_children_recursive(&node_1, |a, b| a.get_value().cmp(&b.get_value()))?;
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.traverse(TraversalStrategy::PreOrder, &node_1)?,
+            vec![1, 3, 2, 6, 5, 4]
+        );
+        assert!(tree
+            .sort_children_by(&7, |a, b| a.get_node_id().cmp(&b.get_node_id()))
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_node_missing_parent() {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let result = tree.add_node(Node::new(2, Some(3)), Some(&3));
+        assert!(matches!(result, Err(NodeNotFound(id)) if id == "3"));
+        assert_eq!(tree.get_nodes().len(), 1);
+        assert!(tree.get_node_by_id(&2).is_none());
+        assert!(tree
+            .get_node_by_id(&1)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_tree_add_node_duplicate_id() {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        tree.add_node(Node::new(2, Some(3)), Some(&1)).unwrap();
+        let result = tree.add_node(Node::new(2, Some(4)), Some(&1));
+        assert!(matches!(result, Err(DuplicateNodeId(id)) if id == "2"));
+        assert_eq!(tree.get_nodes().len(), 2);
+        assert_eq!(tree.get_node_by_id(&1).unwrap().get_children_ids(), vec![2]);
+        assert_eq!(tree.get_node_by_id(&2).unwrap().get_value(), Some(3));
+
+        let mut subtree = SubTree::new(Some("Sample Tree"));
+        subtree.add_node(Node::new(3, Some(5)), None).unwrap();
+        subtree.add_node(Node::new(1, Some(6)), Some(&3)).unwrap();
+        let result = tree.add_subtree(&2, subtree);
+        assert!(matches!(result, Err(DuplicateNodeId(id)) if id == "1"));
+        assert_eq!(tree.get_nodes().len(), 2);
+        assert!(tree
+            .get_node_by_id(&2)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+    }
+
     #[test]
     fn test_tree_get_node() {
         let mut tree = Tree::new(Some("Sample Tree"));
@@ -963,6 +2553,110 @@ mod tests {
         assert_eq!(tree.get_node_by_id(&2), None);
     }
 
+    #[test]
+    fn test_tree_get_node_after_mutations() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+        let mut subtree = SubTree::new(Some("Sub Tree"));
+        let node_4 = subtree.add_node(Node::new(4, Some(5)), None)?;
+        subtree.add_node(Node::new(5, Some(6)), Some(&node_4))?;
+        tree.add_subtree(&node_1, subtree)?;
+        assert_eq!(tree.get_node_by_id(&5), Some(Node::new(5, Some(6))));
+        tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+        assert_eq!(tree.get_node_by_id(&node_2), None);
+        assert!(tree.get_node_by_id(&3).is_some());
+        tree.remove_node(&node_4, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(tree.get_node_by_id(&node_4), None);
+        assert_eq!(tree.get_node_by_id(&5), None);
+        assert_eq!(tree.get_nodes().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_node_handles() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+        let handle_1 = tree.get_node_handle(&node_1).unwrap();
+        let handle_2 = tree.get_node_handle(&node_2).unwrap();
+        let handle_3 = tree.get_node_handle(&node_3).unwrap();
+        let handle_4 = tree.get_node_handle(&node_4).unwrap();
+        assert_eq!(
+            tree.get_children_handles(handle_1)
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec![handle_2, handle_4])
+        );
+        assert_eq!(tree.get_parent_handle(handle_3), Some(handle_2));
+        assert_eq!(tree.get_parent_handle(handle_1), None);
+        // Handles only resolve in the tree that issued them.
+        assert!(tree.clone().get_node_by_handle(handle_1).is_none());
+
+        tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+        assert!(tree.get_node_by_handle(handle_2).is_none());
+        assert_eq!(
+            tree.get_children_handles(handle_1)
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec![handle_4, handle_3])
+        );
+        assert_eq!(tree.get_parent_handle(handle_3), Some(handle_1));
+
+        // A new node must not be reachable through the handle of the removed node.
+        let node_5 = tree.add_node(Node::new(5, Some(6)), Some(&node_4))?;
+        let handle_5 = tree.get_node_handle(&node_5).unwrap();
+        assert_ne!(handle_5, handle_2);
+        assert!(tree.get_node_by_handle(handle_2).is_none());
+        assert_eq!(tree.get_node_by_handle(handle_5).unwrap().get_node_id(), 5);
+
+        tree.remove_node(&node_4, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert!(tree.get_node_by_handle(handle_4).is_none());
+        assert!(tree.get_node_by_handle(handle_5).is_none());
+        assert_eq!(
+            tree.get_children_handles(handle_1)
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec![handle_3])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_subtree_handles() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let mut subtree = SubTree::new(Some("Sub Tree"));
+        let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
+        let node_3 = subtree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+        tree.add_subtree(&node_1, subtree)?;
+        let handle_1 = tree.get_node_handle(&node_1).unwrap();
+        let handle_2 = tree.get_node_handle(&node_2).unwrap();
+        let handle_3 = tree.get_node_handle(&node_3).unwrap();
+        assert_eq!(
+            tree.get_children_handles(handle_1)
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec![handle_2])
+        );
+        assert_eq!(tree.get_parent_handle(handle_2), Some(handle_1));
+        assert_eq!(tree.get_parent_handle(handle_3), Some(handle_2));
+
+        let subtree = tree.get_subtree(&node_2, None)?;
+        let handle = subtree.get_node_handle(&node_2).unwrap();
+        let children = subtree
+            .get_children_handles(handle)
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            subtree
+                .get_node_by_handle(children[0])
+                .unwrap()
+                .get_node_id(),
+            node_3
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_tree_get_no_existent_node() {
         let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
@@ -1021,23 +2715,25 @@ mod tests {
         let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
         let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
         let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
-        assert_eq!(tree.get_ancestor_ids(&node_4).unwrap(), vec![2,1]);
-        assert_eq!(tree.get_ancestor_ids(&node_3).unwrap(), vec![2,1]);
+        assert_eq!(tree.get_ancestor_ids(&node_4).unwrap(), vec![2, 1]);
+        assert_eq!(tree.get_ancestor_ids(&node_3).unwrap(), vec![2, 1]);
         assert_eq!(tree.get_ancestor_ids(&node_2).unwrap(), vec![1]);
         assert_eq!(tree.get_ancestor_ids(&node_1).unwrap(), Vec::<i32>::new());
-   }
-  
-   #[should_panic]
-   fn test_tree_get_node_ancestor_ids_no_existent_node() {
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tree_get_node_ancestor_ids_no_existent_node() {
         let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
         tree.get_ancestor_ids(&1).unwrap();
     }
-  
-   #[should_panic]
-   fn test_tree_get_node_depth_no_existent_node() {
+
+    #[test]
+    #[should_panic]
+    fn test_tree_get_node_depth_no_existent_node() {
         let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
         tree.get_node_depth(&1).unwrap();
-   }
+    }
 
     #[test]
     fn test_tree_get_height() {
@@ -1093,6 +2789,149 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_tree_remove_node_returns_subtree() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_3))?;
+        tree.add_node(Node::new(5, Some(7)), Some(&node_1))?;
+
+        let removed = tree.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+        assert_eq!(removed.get_nodes().len(), 1);
+        assert_eq!(removed.get_root_node().unwrap().get_node_id(), node_2);
+        assert!(removed
+            .get_root_node()
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        assert_eq!(
+            tree.get_node_by_id(&node_3).unwrap().get_parent_id(),
+            Some(node_1)
+        );
+
+        let removed = tree.remove_node(&node_3, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(tree.get_nodes().len(), 2);
+        assert_eq!(removed.get_name(), Some("3"));
+        assert_eq!(
+            removed.traverse(TraversalStrategy::PreOrder, &node_3)?,
+            vec![node_3, node_4]
+        );
+        assert_eq!(removed.get_root_node().unwrap().get_node_id(), node_3);
+        assert_eq!(removed.get_root_node().unwrap().get_parent_id(), None);
+
+        // Undo the removal.
+        tree.add_subtree(&node_1, removed)?;
+        assert_eq!(tree.get_nodes().len(), 4);
+        assert_eq!(
+            tree.get_node_by_id(&node_4).unwrap().get_parent_id(),
+            Some(node_3)
+        );
+        assert_eq!(tree.get_node_depth(&node_4)?, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_remove_node_deep_tree() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Deep Tree"));
+        let root = tree.add_node(Node::new(0, Some(0)), None)?;
+        let mut parent = root;
+        for node_id in 1..100_000 {
+            parent = tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent))?;
+        }
+        let removed = tree.remove_node(&1, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(removed.get_nodes().len(), 99_999);
+        assert_eq!(tree.get_nodes().len(), 1);
+        assert!(tree
+            .get_node_by_id(&root)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        assert!(tree.get_node_by_id(&50_000).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_move_node() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        let node_5 = tree.add_node(Node::new(5, Some(7)), Some(&node_4))?;
+
+        tree.move_node(&node_4, &node_3, None)?;
+        tree.debug_assert_valid();
+        assert!(tree
+            .get_node_by_id(&node_2)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        assert_eq!(
+            tree.get_node_by_id(&node_3).unwrap().get_children_ids(),
+            vec![node_4]
+        );
+        assert_eq!(
+            tree.get_ancestor_ids(&node_5)?,
+            vec![node_4, node_3, node_1]
+        );
+        let handle = tree.get_node_handle(&node_4).unwrap();
+        assert_eq!(
+            tree.get_parent_handle(handle),
+            tree.get_node_handle(&node_3)
+        );
+
+        // Reorder the children of the same parent.
+        tree.move_node(&node_3, &node_1, Some(0))?;
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![node_3, node_2]
+        );
+        tree.move_node(&node_3, &node_1, None)?;
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![node_2, node_3]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_move_node_invalid() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+
+        assert!(matches!(
+            tree.move_node(&node_2, &node_3, None),
+            Err(InvalidOperation(_))
+        ));
+        assert!(matches!(
+            tree.move_node(&node_2, &node_2, None),
+            Err(InvalidOperation(_))
+        ));
+        assert!(matches!(
+            tree.move_node(&node_1, &node_3, None),
+            Err(InvalidOperation(_))
+        ));
+        assert!(matches!(
+            tree.move_node(&node_3, &node_1, Some(2)),
+            Err(InvalidOperation(_))
+        ));
+        assert!(matches!(
+            tree.move_node(&node_3, &4, None),
+            Err(NodeNotFound(_))
+        ));
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&node_3).unwrap().get_parent_id(),
+            Some(node_2)
+        );
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_tree_remove_node_no_existent_node() {
@@ -1132,6 +2971,99 @@ mod tests {
         assert_eq!(subsection.get_nodes().len(), 3);
     }
 
+    #[test]
+    fn test_tree_clone() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let mut copy = tree.clone();
+        copy.debug_assert_valid();
+        assert_eq!(copy, tree);
+        copy.get_node_by_id(&node_2).unwrap().set_value(Some(4));
+        copy.add_node(Node::new(3, Some(5)), Some(&node_2))?;
+        assert_eq!(tree.get_node_by_id(&node_2).unwrap().get_value(), Some(3));
+        assert!(tree
+            .get_node_by_id(&node_2)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        // Changing the links of a clone does not change the nodes of the tree either.
+        let mut clone = tree.clone();
+        clone.add_node(Node::new(4, Some(6)), Some(&node_1))?;
+        assert_eq!(
+            tree.get_node_by_id(&node_1).unwrap().get_children_ids(),
+            vec![node_2]
+        );
+        assert!(tree.validate().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_get_subtree_copies_nodes() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3))?;
+
+        let subtree = tree.get_subtree(&node_2, None)?;
+        subtree.debug_assert_valid();
+        assert_eq!(subtree.get_name(), Some("2"));
+        assert_eq!(subtree.get_nodes().len(), 4);
+        assert_eq!(subtree.get_root_node().unwrap().get_node_id(), node_2);
+        subtree.get_node_by_id(&node_3).unwrap().set_value(Some(7));
+        assert_eq!(tree.get_node_by_id(&node_3).unwrap().get_value(), Some(6));
+
+        let subtree = tree.get_subtree(&node_2, Some(1))?;
+        subtree.debug_assert_valid();
+        assert_eq!(subtree.get_nodes().len(), 3);
+        assert!(subtree
+            .get_node_by_id(&node_3)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        assert_eq!(tree.get_subtree(&node_2, Some(0))?.get_nodes().len(), 1);
+
+        // Changing the links of a subsection does not change the tree.
+        let mut subtree = tree.get_subtree(&node_2, None)?;
+        subtree.add_node(Node::new(6, Some(8)), Some(&node_2))?;
+        subtree.remove_node(&4, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        subtree.debug_assert_valid();
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&4).unwrap().get_parent_id(),
+            Some(node_2)
+        );
+        assert_eq!(
+            tree.traverse(TraversalStrategy::PreOrder, &node_1)?,
+            vec![1, 2, 3, 5, 4]
+        );
+
+        // The copy can be added back without sharing nodes with the tree.
+        tree.remove_node(&node_3, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        let subtree = tree.get_subtree(&node_2, None)?;
+        tree.add_subtree(&node_1, subtree.map_ids(|node_id| node_id * 10)?)?;
+        tree.debug_assert_valid();
+        assert_eq!(tree.get_nodes().len(), 5);
+        assert!(tree.get_subtree(&7, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_get_subsection_deep_chain() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let mut parent = tree.add_node(Node::new(0, Some(0)), None)?;
+        for node_id in 1..20_000 {
+            parent = tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent))?;
+        }
+        assert_eq!(tree.get_subtree(&0, None)?.get_nodes().len(), 20_000);
+        assert_eq!(tree.get_subtree(&10_000, Some(5))?.get_nodes().len(), 6);
+        assert_eq!(tree.get_height()?, 19_999);
+        assert_eq!(tree.get_node_height(&10_000)?, 9_999);
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_tree_get_subsection_no_existent_node() {
@@ -1172,6 +3104,27 @@ mod tests {
         assert_eq!(tree.get_nodes().len(), 3);
     }
 
+    #[test]
+    fn test_tree_add_subsection_leaves_source_unchanged() -> crate::prelude::Result<()> {
+        let mut source = Tree::new(Some("Source"));
+        let node_1 = source.add_node(Node::new(1, Some(1)), None)?;
+        let node_2 = source.add_node(Node::new(2, Some(2)), Some(&node_1))?;
+        source.add_node(Node::new(3, Some(3)), Some(&node_2))?;
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_10 = tree.add_node(Node::new(10, Some(10)), None)?;
+
+        tree.add_subtree(&node_10, source.get_subtree(&node_2, None)?)?;
+        tree.debug_assert_valid();
+        assert!(source.validate().is_empty());
+        assert_eq!(
+            source.get_node_by_id(&node_2).unwrap().get_parent_id(),
+            Some(node_1)
+        );
+        tree.get_node_by_id(&3).unwrap().set_value(Some(4));
+        assert_eq!(source.get_node_by_id(&3).unwrap().get_value(), Some(3));
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_tree_add_subsection_no_root_node() {
@@ -1196,6 +3149,130 @@ mod tests {
         assert_eq!(tree.to_string(), expected_str);
     }
 
+    #[test]
+    fn test_tree_descendant_queries() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        let node_5 = tree.add_node(Node::new(5, Some(7)), Some(&node_2))?;
+        let node_6 = tree.add_node(Node::new(6, Some(8)), Some(&node_3))?;
+        assert_eq!(tree.get_leaf_ids(), vec![node_4, node_5, node_6]);
+        assert_eq!(tree.get_descendant_ids(&node_2)?, vec![node_4, node_5]);
+        assert!(tree.get_descendant_ids(&node_6)?.is_empty());
+        assert_eq!(tree.get_subtree_size(&node_1)?, 6);
+        assert_eq!(tree.get_subtree_size(&node_6)?, 1);
+        assert!(tree.is_ancestor_of(&node_1, &node_6)?);
+        assert!(tree.is_ancestor_of(&node_2, &node_5)?);
+        assert!(!tree.is_ancestor_of(&node_2, &node_6)?);
+        assert!(!tree.is_ancestor_of(&node_2, &node_2)?);
+        assert!(tree.get_descendant_ids(&7).is_err());
+        assert!(tree.get_subtree_size(&7).is_err());
+        assert!(tree.is_ancestor_of(&7, &node_1).is_err());
+        assert!(tree.is_ancestor_of(&node_1, &7).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_level_queries() -> crate::prelude::Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        assert_eq!(forest.get_width(), 0);
+        let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        forest.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+        forest.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        let node_5 = forest.add_node(Node::new(5, Some(7)), None)?;
+        forest.add_node(Node::new(6, Some(8)), Some(&node_5))?;
+        let ids = |nodes: Nodes<i32, i32>| {
+            nodes
+                .iter()
+                .map(|node| node.get_node_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(forest.get_nodes_at_depth(0)), vec![1, 5]);
+        assert_eq!(ids(forest.get_nodes_at_depth(1)), vec![2, 3, 6]);
+        assert_eq!(ids(forest.get_nodes_at_depth(2)), vec![4]);
+        assert!(forest.get_nodes_at_depth(3).is_empty());
+        assert!(forest.get_nodes_at_depth(-1).is_empty());
+        assert_eq!(forest.get_width(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_forest() -> crate::prelude::Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = forest.add_node(Node::new(3, Some(6)), None)?;
+        let node_4 = forest.add_node(Node::new(4, Some(5)), Some(&node_3))?;
+        forest.add_node(Node::new(5, Some(6)), Some(&node_4))?;
+        forest.debug_assert_valid();
+        let roots = forest.get_root_nodes();
+        assert_eq!(
+            roots
+                .iter()
+                .map(|node| node.get_node_id())
+                .collect::<Vec<_>>(),
+            vec![node_1, node_3]
+        );
+        assert_eq!(forest.get_height()?, 2);
+        assert_eq!(
+            forest.traverse_all(TraversalStrategy::PreOrder)?,
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(
+            forest.to_string(),
+            "Sample Forest\n*************\n1: 2\n└── 2: 3\n3: 6\n└── 4: 5\n    └── 5: 6\n"
+        );
+
+        // A tree still only has a single root node.
+        let mut tree = Tree::new(Some("Sample Tree"));
+        tree.add_node(Node::new(1, Some(2)), None)?;
+        assert!(matches!(
+            tree.add_node(Node::new(2, Some(3)), None),
+            Err(RootNodeAlreadyPresent)
+        ));
+        assert!(matches!(tree.promote_to_root(&1), Err(InvalidOperation(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_forest_promote_and_merge() -> crate::prelude::Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = forest.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+
+        forest.promote_to_root(&node_2)?;
+        forest.debug_assert_valid();
+        assert_eq!(forest.get_root_nodes().len(), 2);
+        assert!(forest
+            .get_node_by_id(&node_1)
+            .unwrap()
+            .get_children_ids()
+            .is_empty());
+        assert_eq!(
+            forest.get_parent_handle(forest.get_node_handle(&node_2).unwrap()),
+            None
+        );
+
+        forest.move_node(&node_1, &node_3, None)?;
+        forest.debug_assert_valid();
+        assert_eq!(forest.get_root_nodes().len(), 1);
+        assert_eq!(forest.get_ancestor_ids(&node_1)?, vec![node_3, node_2]);
+
+        // Removing a root node of a forest while retaining its children promotes the children.
+        forest.remove_node(&node_2, NodeRemovalStrategy::RetainChildren)?;
+        forest.debug_assert_valid();
+        assert_eq!(forest.get_root_node().unwrap().get_node_id(), node_3);
+        assert_eq!(
+            forest.get_parent_handle(forest.get_node_handle(&node_3).unwrap()),
+            None
+        );
+        Ok(())
+    }
+
     #[test]
     fn compare_tree() {
         let mut tree = Tree::new(Some("Sample Tree"));
@@ -1245,6 +3322,80 @@ mod tests {
             .unwrap();
         let expected_post_order = vec![node_4, node_5, node_2, node_6, node_3, node_1];
         assert_eq!(post_order_nodes, expected_post_order);
+
+        let level_order_nodes = tree
+            .traverse(TraversalStrategy::LevelOrder, &node_1)
+            .unwrap();
+        let expected_level_order = vec![node_1, node_2, node_3, node_4, node_5, node_6];
+        assert_eq!(level_order_nodes, expected_level_order);
+
+        let reverse_level_order_nodes = tree
+            .traverse(TraversalStrategy::ReverseLevelOrder, &node_1)
+            .unwrap();
+        let expected_reverse_level_order = vec![node_4, node_5, node_6, node_2, node_3, node_1];
+        assert_eq!(reverse_level_order_nodes, expected_reverse_level_order);
+
+        let zigzag_nodes = tree.traverse(TraversalStrategy::ZigZag, &node_1).unwrap();
+        let expected_zigzag = vec![node_1, node_3, node_2, node_4, node_5, node_6];
+        assert_eq!(zigzag_nodes, expected_zigzag);
+    }
+
+    #[test]
+    fn test_tree_traverse_in_order() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        tree.add_node(Node::new(1, Some(1)), None)?;
+        for (node_id, parent_id) in [(2, 1), (3, 1), (4, 2), (5, 2), (8, 2), (6, 3), (7, 3)] {
+            tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent_id))?;
+        }
+        tree.add_node(Node::new(9, Some(9)), Some(&5))?;
+        tree.add_node(Node::new(10, Some(10)), Some(&6))?;
+        assert_eq!(
+            tree.traverse(TraversalStrategy::InOrder, &1)?,
+            vec![4, 2, 5, 9, 8, 1, 3, 10, 6, 7]
+        );
+        assert_eq!(
+            tree.traverse(TraversalStrategy::InOrder, &3)?,
+            vec![10, 6, 3, 7]
+        );
+        assert!(tree.traverse(TraversalStrategy::InOrder, &7)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_traverse_in_order_deep_chain() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Deep Tree"));
+        let mut parent = tree.add_node(Node::new(0, Some(0)), None)?;
+        for node_id in 1..200_000 {
+            parent = tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent))?;
+        }
+        let nodes = tree.traverse(TraversalStrategy::InOrder, &0)?;
+        assert_eq!(nodes.len(), 200_000);
+        assert!(nodes.iter().rev().copied().eq(0..200_000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_traverse_levels() {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1)).unwrap();
+        let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        let node_5 = tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
+        let levels = tree.traverse_levels(&node_1).unwrap();
+        assert_eq!(
+            levels,
+            vec![vec![node_1], vec![node_2, node_3], vec![node_4, node_5]]
+        );
+        let levels = tree.traverse_levels(&node_2).unwrap();
+        assert_eq!(levels, vec![vec![node_2], vec![node_4]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tree_traverse_levels_no_existent_node() {
+        let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        tree.traverse_levels(&1).unwrap();
     }
 
     #[cfg(feature = "serde")]
@@ -1263,6 +3414,105 @@ mod tests {
         assert_eq!(deserialized, expected_tree);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tree_deserialize_rebuilds_index() {
+        let serialized = r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[2]},{"node_id":2,"value":3,"parent":1,"children":[]}]}"#;
+        let mut tree: Tree<u32, u32> = serde_json::from_str(serialized).unwrap();
+        assert_eq!(tree.get_node_by_id(&2).unwrap().get_parent_id(), Some(1));
+        tree.add_node(Node::new(3, Some(4)), Some(&2)).unwrap();
+        assert_eq!(tree.get_node_by_id(&2).unwrap().get_children_ids(), vec![3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tree_deserialize_rejects_invalid_trees() {
+        let invalid = [
+            (
+                r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[2]}]}"#,
+                "Child 2 of node 1 is not in the tree.",
+            ),
+            (
+                r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[2]},{"node_id":2,"value":3,"parent":null,"children":[]}]}"#,
+                "Node 1 lists child 2 but the child has another parent.",
+            ),
+            (
+                r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[]},{"node_id":1,"value":3,"parent":null,"children":[]}]}"#,
+                "Node 1 is present more than once.",
+            ),
+            (
+                r#"{"nodes":[{"node_id":1,"value":2,"parent":null,"children":[]},{"node_id":2,"value":3,"parent":null,"children":[]}]}"#,
+                "Nodes 1, 2 are all root nodes.",
+            ),
+        ];
+        for (serialized, violation) in invalid {
+            let error = serde_json::from_str::<Tree<u32, u32>>(serialized).unwrap_err();
+            assert!(
+                error.to_string().contains(violation),
+                "{} does not mention {}",
+                error,
+                violation
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_subtree_serialize_and_deserialize() {
+        let mut tree = Tree::new(None);
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None).unwrap();
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1)).unwrap();
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2)).unwrap();
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3)).unwrap();
+        for (generations, node_ids) in [(None, vec![2, 3, 5, 4]), (Some(1), vec![2, 3, 4])] {
+            let subtree = tree.get_subtree(&node_2, generations).unwrap();
+            subtree.debug_assert_valid();
+            let serialized = serde_json::to_string(&subtree).unwrap();
+            let deserialized: SubTree<u32, u32> = serde_json::from_str(&serialized).unwrap();
+            deserialized.debug_assert_valid();
+            assert_eq!(deserialized, subtree);
+            let ids: Vec<u32> = deserialized
+                .get_nodes()
+                .iter()
+                .map(|node| node.get_node_id())
+                .collect();
+            assert_eq!(ids, node_ids);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_unchecked_parts() {
+        let serialized = r#"[{"node_id":1,"value":2,"parent":null,"children":[2]}]"#;
+        let nodes: Nodes<u32, u32> = serde_json::from_str(serialized).unwrap();
+        let tree = Tree::from_unchecked_parts(None, nodes, false);
+        assert_eq!(tree.get_nodes().len(), 1);
+        assert!(matches!(
+            tree.validate().as_slice(),
+            [Violation::MissingChild {
+                node_id: 1,
+                child_id: 2
+            }]
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_forest_serialize_and_deserialize() {
+        let mut forest = Tree::new_forest(None);
+        forest.add_node(Node::new(1, Some(2)), None).unwrap();
+        forest.add_node(Node::new(2, Some(3)), None).unwrap();
+        let serialized = serde_json::to_string(&forest).unwrap();
+        let expected = r#"{"nodes":[{"node_id":1,"value":2,"children":[],"parent":null},{"node_id":2,"value":3,"children":[],"parent":null}],"forest":true}"#;
+        assert_eq!(serialized, expected);
+        let mut deserialized: Tree<u32, u32> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, forest);
+        assert!(deserialized.is_forest());
+        deserialized.add_node(Node::new(3, Some(4)), None).unwrap();
+        assert_eq!(deserialized.get_root_nodes().len(), 3);
+    }
+
     #[allow(deprecated)] // This is solely for testing hashing in no_std.
     #[test]
     fn test_hashing() {
@@ -1295,4 +3545,48 @@ mod tests {
         let tree_2_hash = hasher.finish();
         assert_eq!(tree_hash, tree_2_hash);
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_tree_is_send_and_sync() {
+        fn assert_send_sync<V: Send + Sync>() {}
+        assert_send_sync::<Tree<i32, String>>();
+        assert_send_sync::<Node<i32, String>>();
+        assert_send_sync::<ValueIndex<i32, String>>();
+    }
+
+    #[cfg(all(feature = "async", not(feature = "no_std")))]
+    #[test]
+    fn test_tree_shared_across_threads() -> crate::prelude::Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(0)), None)?;
+        for i in 2..=9 {
+            tree.add_node(Node::new(i, Some(0)), Some(&node_1))?;
+        }
+        let index = tree.create_value_index(IndexMode::Multi)?;
+        let tree = &tree;
+        std::thread::scope(|scope| {
+            for i in 2..=9 {
+                scope.spawn(move || {
+                    let node = tree.get_node_by_id(&i).unwrap();
+                    for _ in 0..100 {
+                        node.update_value(|value| *value += 1);
+                    }
+                    assert_eq!(tree.get_ancestor_ids(&i).unwrap(), vec![1]);
+                });
+            }
+            scope.spawn(move || {
+                for _ in 0..100 {
+                    let _ = tree.traverse(TraversalStrategy::PreOrder, &node_1).unwrap();
+                }
+            });
+        });
+        for i in 2..=9 {
+            assert_eq!(tree.get_node_by_id(&i).unwrap().get_value(), Some(100));
+        }
+        assert_eq!(index.get_all(&100).len(), 8);
+        assert_eq!(index.get_all(&0), vec![1]);
+        tree.debug_assert_valid();
+        Ok(())
+    }
 }
@@ -0,0 +1,525 @@
+use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer};
+
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::Node;
+use crate::tree::IdMap;
+
+/// The identity of the next arena that is created.
+static NEXT_ARENA_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A handle to a node stored in a tree.
+///
+/// A node handle is a lightweight, copyable reference to a node in a tree. It is made up of the
+/// identity of the tree, the index of the slot that holds the node and the generation of that slot.
+/// When a node is removed from the tree the generation of its slot is bumped, so any handle that still
+/// points at the slot is rejected instead of resolving to whichever node reuses the slot later.
+///
+/// Handles are obtained from [`Tree::get_node_handle`](crate::prelude::Tree::get_node_handle) and only
+/// resolve in the tree that issued them. Other trees, clones of the tree included, do not resolve them.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+/// let node_id = tree.add_node(Node::new(1, Some(2)), None)?;
+/// let handle = tree.get_node_handle(&node_id).unwrap();
+/// assert_eq!(tree.get_node_by_handle(handle).unwrap().get_value(), Some(2));
+/// assert!(tree.clone().get_node_by_handle(handle).is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeHandle {
+    arena: usize,
+    index: usize,
+    generation: u32,
+}
+
+/// An iterator over the handles of the children of a node.
+///
+/// This struct is created by the
+/// [`Tree::get_children_handles`](crate::prelude::Tree::get_children_handles) method.
+#[derive(Clone, Debug)]
+pub struct ChildHandles<'a> {
+    arena: usize,
+    slots: &'a [Slot],
+    children: Iter<'a, usize>,
+}
+
+impl ChildHandles<'_> {
+    /// Get the handle of the node in a slot.
+    fn handle(&self, index: usize) -> NodeHandle {
+        NodeHandle {
+            arena: self.arena,
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+}
+
+impl Iterator for ChildHandles<'_> {
+    type Item = NodeHandle;
+
+    /// Get the handle of the next child.
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = *self.children.next()?;
+        Some(self.handle(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.children.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for ChildHandles<'_> {
+    /// Get the handle of the previous child.
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = *self.children.next_back()?;
+        Some(self.handle(index))
+    }
+}
+
+impl ExactSizeIterator for ChildHandles<'_> {}
+
+/// A slot of the arena, pointing at the position of its node.
+#[derive(Clone, Copy, Debug)]
+struct Slot {
+    generation: u32,
+    position: Option<usize>,
+}
+
+/// The links of a node, given as the slots of the nodes.
+#[derive(Clone, Debug)]
+struct Links {
+    slot: usize,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+/// The storage of the nodes of a tree.
+///
+/// The nodes are packed in the order they were added to the tree, next to the links to their parent
+/// and children. Handles address the nodes through a table of slots holding the position of every
+/// node. Freed slots are reused for new nodes. Every time a slot is freed its generation is bumped so
+/// that handles to the old node no longer resolve. A slot whose generation is exhausted is retired
+/// rather than reused.
+///
+/// The arena is the only place the structure of the tree is kept. Every change to the links goes
+/// through the arena, which writes the ids of the parent and children back to the nodes so that
+/// `Node::get_parent_id` and `Node::get_children_ids` agree with the tree.
+pub(crate) struct Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// The identity of the arena, carried by its handles.
+    id: usize,
+    nodes: Nodes<Q, T>,
+    /// The links of the nodes, in the same order as the nodes.
+    links: Vec<Links>,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+    /// The slots of the nodes by id.
+    index: IdMap<Q, usize>,
+}
+
+impl<Q, T> Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create an empty arena with a new identity.
+    pub(crate) fn new() -> Self {
+        Self {
+            // Identities are only compared with each other, so wrapping around is harmless.
+            id: NEXT_ARENA_ID.fetch_add(1, AtomicOrdering::Relaxed),
+            nodes: Nodes::default(),
+            links: vec![],
+            slots: vec![],
+            free: vec![],
+            index: IdMap::new(),
+        }
+    }
+
+    /// Get the nodes in the order they were added.
+    pub(crate) fn nodes(&self) -> &Nodes<Q, T> {
+        &self.nodes
+    }
+
+    /// Get the number of nodes in the arena.
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Get the handles of the nodes in the order they were added.
+    pub(crate) fn handles(&self) -> impl Iterator<Item = NodeHandle> + '_ {
+        self.links.iter().map(|links| self.handle_at(links.slot))
+    }
+
+    /// Get the handle of the node in a slot.
+    fn handle_at(&self, index: usize) -> NodeHandle {
+        NodeHandle {
+            arena: self.id,
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Get the position of a node from its handle.
+    fn position(&self, handle: NodeHandle) -> Option<usize> {
+        if handle.arena != self.id {
+            return None;
+        }
+        self.slots
+            .get(handle.index)
+            .filter(|slot| slot.generation == handle.generation)
+            .and_then(|slot| slot.position)
+    }
+
+    /// Get the position of the node in a slot. The slot must hold a node.
+    fn position_of_slot(&self, index: usize) -> usize {
+        self.slots[index]
+            .position
+            .expect("A linked slot always holds a node.")
+    }
+
+    /// Get a node from its handle.
+    pub(crate) fn get(&self, handle: NodeHandle) -> Option<&Node<Q, T>> {
+        self.position(handle)
+            .map(|position| &self.nodes.0[position])
+    }
+
+    /// Get the handle of the parent of a node.
+    pub(crate) fn parent(&self, handle: NodeHandle) -> Option<NodeHandle> {
+        let position = self.position(handle)?;
+        self.links[position]
+            .parent
+            .map(|parent| self.handle_at(parent))
+    }
+
+    /// Get the handles of the children of a node.
+    pub(crate) fn children(&self, handle: NodeHandle) -> Option<ChildHandles<'_>> {
+        let position = self.position(handle)?;
+        Some(ChildHandles {
+            arena: self.id,
+            slots: &self.slots,
+            children: self.links[position].children.iter(),
+        })
+    }
+
+    /// Get the number of children of a node.
+    pub(crate) fn children_count(&self, handle: NodeHandle) -> Option<usize> {
+        self.position(handle)
+            .map(|position| self.links[position].children.len())
+    }
+
+    /// Write the ids of the parent and children of a node to the node.
+    fn sync(&self, index: usize) {
+        let node_id = |index: usize| self.nodes.0[self.position_of_slot(index)].get_node_id();
+        let position = self.position_of_slot(index);
+        let links = &self.links[position];
+        let node = &self.nodes.0[position];
+        node.set_parent_id(links.parent.map(node_id));
+        node.set_children_ids(links.children.iter().map(|child| node_id(*child)).collect());
+    }
+
+    /// Link a node under a new parent at the given position among its children, or after the last
+    /// child if no position is given.
+    ///
+    /// The node must not have a parent. The position is capped at the number of children.
+    pub(crate) fn attach(&mut self, handle: NodeHandle, parent: NodeHandle, index: Option<usize>) {
+        let (Some(position), Some(parent_position)) =
+            (self.position(handle), self.position(parent))
+        else {
+            return;
+        };
+        let children = &mut self.links[parent_position].children;
+        let index = index.map_or(children.len(), |index| index.min(children.len()));
+        children.insert(index, handle.index);
+        self.links[position].parent = Some(parent.index);
+        self.sync(handle.index);
+        // Only add the new child to the parent, so that building wide trees stays linear.
+        let child_id = self.nodes.0[position].get_node_id();
+        self.nodes.0[parent_position].insert_child_id(index, child_id);
+    }
+
+    /// Unlink a node from its parent. The node becomes a root node.
+    pub(crate) fn detach(&mut self, handle: NodeHandle) {
+        let Some(position) = self.position(handle) else {
+            return;
+        };
+        if let Some(parent) = self.links[position].parent.take() {
+            let parent_position = self.position_of_slot(parent);
+            self.links[parent_position]
+                .children
+                .retain(|child| *child != handle.index);
+            self.sync(parent);
+        }
+        self.sync(handle.index);
+    }
+
+    /// Reorder the children of a node. The handles must be the handles of the children of the node.
+    pub(crate) fn reorder_children(&mut self, handle: NodeHandle, children: &[NodeHandle]) {
+        if let Some(position) = self.position(handle) {
+            self.links[position].children = children.iter().map(|child| child.index).collect();
+            self.sync(handle.index);
+        }
+    }
+
+    /// Remove all the nodes from the arena.
+    pub(crate) fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            slot.position = None;
+        }
+        self.nodes = Nodes::default();
+        self.links.clear();
+        self.free.clear();
+        self.index.clear();
+    }
+}
+
+impl<Q, T> Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Get the handle of a node from its id.
+    pub(crate) fn get_handle(&self, node_id: &Q) -> Option<NodeHandle> {
+        self.index.get(node_id).map(|index| self.handle_at(*index))
+    }
+
+    /// Check if a node with the given id is in the arena.
+    pub(crate) fn contains(&self, node_id: &Q) -> bool {
+        self.index.contains_key(node_id)
+    }
+
+    /// Add a node to the arena without linking it to any other node and return its handle.
+    pub(crate) fn insert(&mut self, node: Node<Q, T>) -> NodeHandle {
+        let position = self.nodes.len();
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].position = Some(position);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    position: Some(position),
+                });
+                self.slots.len() - 1
+            }
+        };
+        // The first node with an id is the one found by its id.
+        self.index.entry(node.get_node_id()).or_insert(index);
+        self.nodes.push(node);
+        self.links.push(Links {
+            slot: index,
+            parent: None,
+            children: vec![],
+        });
+        self.handle_at(index)
+    }
+
+    /// Link a node to its parent and children using the ids held by the node.
+    ///
+    /// Ids of nodes that are not in the arena are skipped. The ids held by the node are left as they
+    /// are, so they still show the links that could not be made.
+    pub(crate) fn link_from_ids(&mut self, handle: NodeHandle) {
+        let Some(position) = self.position(handle) else {
+            return;
+        };
+        let node = &self.nodes.0[position];
+        let parent = node
+            .get_parent_id()
+            .and_then(|parent_id| self.index.get(&parent_id).copied());
+        let children = node
+            .get_children_ids()
+            .iter()
+            .filter_map(|child_id| self.index.get(child_id).copied())
+            .collect();
+        let links = &mut self.links[position];
+        links.parent = parent;
+        links.children = children;
+    }
+
+    /// Remove nodes from the arena and return them, invalidating their handles.
+    ///
+    /// The links between the removed nodes are kept on the nodes, while the links from the remaining
+    /// nodes to the removed ones are dropped.
+    pub(crate) fn remove_all(&mut self, handles: &[NodeHandle]) -> Vec<Node<Q, T>> {
+        let mut removed = vec![];
+        for handle in handles {
+            if let Some(position) = self.position(*handle) {
+                let slot = &mut self.slots[handle.index];
+                slot.position = None;
+                if let Some(generation) = slot.generation.checked_add(1) {
+                    slot.generation = generation;
+                    self.free.push(handle.index);
+                }
+                let node = self.nodes.0[position].clone();
+                let node_id = node.get_node_id();
+                if self.index.get(&node_id) == Some(&handle.index) {
+                    self.index.remove(&node_id);
+                }
+                removed.push(node);
+            }
+        }
+
+        // Pack the remaining nodes, keeping their order.
+        let slots = &mut self.slots;
+        let mut kept = 0;
+        for position in 0..self.links.len() {
+            let slot = self.links[position].slot;
+            if slots[slot].position.is_some() {
+                self.links.swap(kept, position);
+                self.nodes.0.swap(kept, position);
+                slots[slot].position = Some(kept);
+                kept += 1;
+            }
+        }
+        self.links.truncate(kept);
+        self.nodes.0.truncate(kept);
+        for links in self.links.iter_mut() {
+            links.parent = links
+                .parent
+                .filter(|parent| slots[*parent].position.is_some());
+            links
+                .children
+                .retain(|child| slots[*child].position.is_some());
+        }
+        removed
+    }
+}
+
+impl<Q, T> Clone for Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Copy the arena and its nodes under a new identity, so that the handles of the original do not
+    /// resolve in the copy and changing the copy does not change the nodes of the original.
+    fn clone(&self) -> Self {
+        Self {
+            id: Self::new().id,
+            nodes: Nodes::new(self.nodes.iter().map(Node::deep_clone).collect()),
+            links: self.links.clone(),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<Q, T> Default for Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create an empty arena.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<Q, T> Serialize for Arena<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Serialize,
+    T: PartialEq + Eq + Clone + Serialize,
+{
+    /// Serialize the nodes of the arena.
+    fn serialize<S>(&self, serializer: S) -> crate::lib::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.nodes.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_insert_and_link() {
+        let mut arena = Arena::new();
+        let parent = arena.insert(Node::new(1, Some(2)));
+        let first = arena.insert(Node::new(2, Some(3)));
+        let second = arena.insert(Node::new(3, Some(4)));
+        arena.attach(second, parent, None);
+        arena.attach(first, parent, Some(0));
+        assert_eq!(arena.get(first).unwrap().get_value(), Some(3));
+        assert_eq!(arena.parent(first), Some(parent));
+        assert_eq!(
+            arena.children(parent).unwrap().collect::<Vec<_>>(),
+            vec![first, second]
+        );
+        // The ids held by the nodes follow the links of the arena.
+        assert_eq!(arena.get(parent).unwrap().get_children_ids(), vec![2, 3]);
+        assert_eq!(arena.get(second).unwrap().get_parent_id(), Some(1));
+
+        arena.detach(first);
+        assert_eq!(arena.parent(first), None);
+        assert_eq!(arena.get(parent).unwrap().get_children_ids(), vec![3]);
+    }
+
+    #[test]
+    fn test_arena_remove_invalidates_handle() {
+        let mut arena = Arena::new();
+        let first = arena.insert(Node::new(1, Some(2)));
+        let second = arena.insert(Node::new(2, Some(3)));
+        arena.attach(second, first, None);
+        assert_eq!(arena.remove_all(&[first]), vec![Node::new(1, Some(2))]);
+        assert!(arena.get(first).is_none());
+        assert!(arena.remove_all(&[first]).is_empty());
+        assert_eq!(arena.parent(second), None);
+        assert_eq!(arena.get_handle(&2), Some(second));
+        // The slot is reused but the old handle must not resolve to the new node.
+        let third = arena.insert(Node::new(3, Some(4)));
+        assert_eq!(third.index, first.index);
+        assert_ne!(third, first);
+        assert!(arena.get(first).is_none());
+        assert_eq!(arena.get(third).unwrap().get_node_id(), 3);
+        assert_eq!(arena.nodes().len(), 2);
+    }
+
+    #[test]
+    fn test_arena_retires_exhausted_slot() {
+        let mut arena = Arena::new();
+        let handle = arena.insert(Node::new(1, Some(2)));
+        arena.slots[handle.index].generation = u32::MAX;
+        let handle = arena.get_handle(&1).unwrap();
+        assert_eq!(arena.remove_all(&[handle]).len(), 1);
+        let next = arena.insert(Node::new(2, Some(3)));
+        assert_ne!(next.index, handle.index);
+    }
+
+    #[test]
+    fn test_arena_rejects_foreign_handles() {
+        let mut arena = Arena::new();
+        let handle = arena.insert(Node::new(1, Some(2)));
+        let mut other = Arena::new();
+        other.insert(Node::new(1, Some(2)));
+        assert!(other.get(handle).is_none());
+        assert!(arena.clone().get(handle).is_none());
+    }
+
+    #[test]
+    fn test_arena_clone_copies_nodes() {
+        let mut arena = Arena::new();
+        let handle = arena.insert(Node::new(1, Some(2)));
+        let clone = arena.clone();
+        clone
+            .get(clone.get_handle(&1).unwrap())
+            .unwrap()
+            .set_value(Some(3));
+        assert_eq!(arena.get(handle).unwrap().get_value(), Some(2));
+    }
+}
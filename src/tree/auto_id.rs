@@ -0,0 +1,739 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::Error::InvalidOperation;
+use crate::lib::*;
+use crate::node::{Lock, MaybeSync};
+use crate::prelude::{Node, Result, Tree};
+
+/// A generator of node ids.
+///
+/// A tree uses an id generator to allocate the ids of the nodes added with `Tree::add_value`. The
+/// built-in generators are `SequentialIds`, `RandomIds` and `PrefixedIds`. Implement this trait to
+/// plug in your own generator with `Tree::set_custom_id_generator`.
+///
+/// This is available only when the `auto_id` feature is enabled.
+pub trait IdGenerator<Q>: MaybeSync {
+    /// Generate the next id.
+    ///
+    /// An error is returned if the generator cannot generate any more ids.
+    fn next_id(&mut self) -> Result<Q>;
+
+    /// Reset the generator so that it generates the same ids again.
+    fn reset(&mut self);
+}
+
+/// An id type that the built-in id generators can generate.
+///
+/// The methods return `None` when the generated id cannot be represented by the type, for instance
+/// when a sequential id does not fit in an `i32`. Both methods return `None` by default, so the
+/// trait can be implemented for id types used only with custom id generators.
+///
+/// This is available only when the `auto_id` feature is enabled.
+pub trait GeneratedId: Sized {
+    /// Create an id from a generated number.
+    fn from_number(number: u128) -> Option<Self> {
+        let _ = number;
+        None
+    }
+
+    /// Create an id from a prefix and a generated number.
+    fn from_prefixed(prefix: &str, number: u128) -> Option<Self> {
+        let _ = (prefix, number);
+        None
+    }
+}
+
+macro_rules! impl_generated_id {
+    ($($ty:ty),*) => {
+        $(
+            impl GeneratedId for $ty {
+                fn from_number(number: u128) -> Option<Self> {
+                    Self::try_from(number).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_generated_id!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl GeneratedId for String {
+    fn from_number(number: u128) -> Option<Self> {
+        Some(number.to_string())
+    }
+
+    fn from_prefixed(prefix: &str, number: u128) -> Option<Self> {
+        Some(format!("{}{}", prefix, number))
+    }
+}
+
+/// Convert a generated id, failing if it cannot be represented by the id type.
+#[doc(hidden)]
+fn convert<Q>(id: Option<Q>, number: u128) -> Result<Q> {
+    id.ok_or(InvalidOperation(format!(
+        "The generated id {} cannot be represented by the node id type.",
+        number
+    )))
+}
+
+/// Advance a counter, failing once every number has been used.
+#[doc(hidden)]
+fn advance(counter: &mut u128) -> Result<()> {
+    *counter = counter.checked_add(1).ok_or(InvalidOperation(String::from(
+        "The id generator ran out of ids.",
+    )))?;
+    Ok(())
+}
+
+/// An id generator counting up from a starting number.
+///
+/// The ids are `start`, `start + 1`, `start + 2`, and so on. An error is returned instead of wrapping
+/// around once the ids no longer fit in the node id type.
+///
+/// This is available only when the `auto_id` feature is enabled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SequentialIds {
+    start: u128,
+    next: u128,
+}
+
+impl SequentialIds {
+    /// Create a sequential id generator starting from the given number.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first id to generate.
+    ///
+    /// # Returns
+    ///
+    /// The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, SequentialIds};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut ids = SequentialIds::new(10);
+    /// assert_eq!(IdGenerator::<i32>::next_id(&mut ids)?, 10);
+    /// assert_eq!(IdGenerator::<i32>::next_id(&mut ids)?, 11);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(start: u128) -> Self {
+        Self { start, next: start }
+    }
+}
+
+impl Default for SequentialIds {
+    /// Create a sequential id generator starting from 1.
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<Q> IdGenerator<Q> for SequentialIds
+where
+    Q: GeneratedId,
+{
+    fn next_id(&mut self) -> Result<Q> {
+        let id = convert(Q::from_number(self.next), self.next)?;
+        advance(&mut self.next)?;
+        Ok(id)
+    }
+
+    fn reset(&mut self) {
+        self.next = self.start;
+    }
+}
+
+/// An id generator of random 128-bit ids.
+///
+/// The ids are computed by scrambling a counter with the seed, so a generator never generates the
+/// same id twice and two generators with the same seed generate the same ids.
+///
+/// This is available only when the `auto_id` feature is enabled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RandomIds {
+    seed: u128,
+    counter: u128,
+}
+
+impl RandomIds {
+    /// Create a random id generator with a random seed.
+    ///
+    /// # Returns
+    ///
+    /// The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, RandomIds};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut ids = RandomIds::new();
+    /// let id: u128 = ids.next_id()?;
+    /// assert_ne!(IdGenerator::<u128>::next_id(&mut ids)?, id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This is not available in `no_std` environments, use `RandomIds::with_seed` instead.
+    #[cfg(not(feature = "no_std"))]
+    pub fn new() -> Self {
+        use std::collections::hash_map::RandomState;
+        use std::hash::BuildHasher;
+
+        let state = RandomState::new();
+        let high = state.hash_one(0u8) as u128;
+        let low = state.hash_one(1u8) as u128;
+        Self::with_seed((high << 64) | low)
+    }
+
+    /// Create a random id generator with the given seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the generator.
+    ///
+    /// # Returns
+    ///
+    /// The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, RandomIds};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut ids = RandomIds::with_seed(42);
+    /// let mut same_ids = RandomIds::with_seed(42);
+    /// let id: u128 = ids.next_id()?;
+    /// assert_eq!(IdGenerator::<u128>::next_id(&mut same_ids)?, id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_seed(seed: u128) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    /// Scramble a number. Every step can be undone, so different numbers give different results.
+    #[doc(hidden)]
+    fn scramble(mut number: u128) -> u128 {
+        number ^= number >> 67;
+        number = number.wrapping_mul(0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c835);
+        number ^= number >> 61;
+        number = number.wrapping_mul(0xd6e8_feb8_6659_fd93_c2b2_ae3d_27d4_eb4f);
+        number ^ (number >> 64)
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Default for RandomIds {
+    /// Create a random id generator with a random seed.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Q> IdGenerator<Q> for RandomIds
+where
+    Q: GeneratedId,
+{
+    fn next_id(&mut self) -> Result<Q> {
+        let number = Self::scramble(self.counter ^ self.seed);
+        let id = convert(Q::from_number(number), number)?;
+        advance(&mut self.counter)?;
+        Ok(id)
+    }
+
+    fn reset(&mut self) {
+        self.counter = 0;
+    }
+}
+
+/// An id generator of string ids made of a prefix and a counter.
+///
+/// The ids are the prefix followed by `start`, `start + 1`, `start + 2`, and so on.
+///
+/// This is available only when the `auto_id` feature is enabled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PrefixedIds {
+    prefix: String,
+    start: u128,
+    next: u128,
+}
+
+impl PrefixedIds {
+    /// Create a prefixed id generator counting up from 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the ids.
+    ///
+    /// # Returns
+    ///
+    /// The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, PrefixedIds};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut ids = PrefixedIds::new("node-");
+    /// assert_eq!(IdGenerator::<String>::next_id(&mut ids)?, "node-1");
+    /// assert_eq!(IdGenerator::<String>::next_id(&mut ids)?, "node-2");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(prefix: &str) -> Self {
+        Self::starting_at(prefix, 1)
+    }
+
+    /// Create a prefixed id generator counting up from the given number.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix of the ids.
+    /// * `start` - The number of the first id.
+    ///
+    /// # Returns
+    ///
+    /// The id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, PrefixedIds};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut ids = PrefixedIds::starting_at("node-", 0);
+    /// assert_eq!(IdGenerator::<String>::next_id(&mut ids)?, "node-0");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn starting_at(prefix: &str, start: u128) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            start,
+            next: start,
+        }
+    }
+}
+
+impl<Q> IdGenerator<Q> for PrefixedIds
+where
+    Q: GeneratedId,
+{
+    fn next_id(&mut self) -> Result<Q> {
+        let id = convert(Q::from_prefixed(&self.prefix, self.next), self.next)?;
+        advance(&mut self.next)?;
+        Ok(id)
+    }
+
+    fn reset(&mut self) {
+        self.next = self.start;
+    }
+}
+
+/// One of the built-in id generators.
+///
+/// The state of a built-in id generator set with `Tree::set_id_generator` is serialized along with
+/// the tree, so a deserialized tree carries on where the generator left off.
+///
+/// This is available only when the `auto_id` feature is enabled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BuiltinIdGenerator {
+    /// A `SequentialIds` generator.
+    Sequential(SequentialIds),
+    /// A `RandomIds` generator.
+    Random(RandomIds),
+    /// A `PrefixedIds` generator.
+    Prefixed(PrefixedIds),
+}
+
+impl From<SequentialIds> for BuiltinIdGenerator {
+    fn from(generator: SequentialIds) -> Self {
+        BuiltinIdGenerator::Sequential(generator)
+    }
+}
+
+impl From<RandomIds> for BuiltinIdGenerator {
+    fn from(generator: RandomIds) -> Self {
+        BuiltinIdGenerator::Random(generator)
+    }
+}
+
+impl From<PrefixedIds> for BuiltinIdGenerator {
+    fn from(generator: PrefixedIds) -> Self {
+        BuiltinIdGenerator::Prefixed(generator)
+    }
+}
+
+impl<Q> IdGenerator<Q> for BuiltinIdGenerator
+where
+    Q: GeneratedId,
+{
+    fn next_id(&mut self) -> Result<Q> {
+        match self {
+            BuiltinIdGenerator::Sequential(generator) => generator.next_id(),
+            BuiltinIdGenerator::Random(generator) => generator.next_id(),
+            BuiltinIdGenerator::Prefixed(generator) => generator.next_id(),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            BuiltinIdGenerator::Sequential(generator) => IdGenerator::<Q>::reset(generator),
+            BuiltinIdGenerator::Random(generator) => IdGenerator::<Q>::reset(generator),
+            BuiltinIdGenerator::Prefixed(generator) => IdGenerator::<Q>::reset(generator),
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+type Shared<V> = Rc<V>;
+
+#[cfg(feature = "async")]
+type Shared<V> = Arc<V>;
+
+/// The id generator of a tree.
+#[derive(Clone)]
+pub(crate) enum TreeIdGenerator<Q> {
+    /// A built-in generator, saved along with the tree.
+    Builtin(BuiltinIdGenerator),
+    /// A custom generator, shared by the clones of the tree. It cannot be saved along with the tree.
+    Custom(Shared<Lock<Box<dyn IdGenerator<Q>>>>),
+}
+
+#[cfg(feature = "serde")]
+impl<Q> Serialize for TreeIdGenerator<Q> {
+    /// Serialize the state of a built-in generator. Custom generators cannot be serialized.
+    fn serialize<S>(&self, serializer: S) -> crate::lib::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            TreeIdGenerator::Builtin(generator) => serializer.serialize_some(generator),
+            TreeIdGenerator::Custom(_) => Err(serde::ser::Error::custom(
+                "A tree with a custom id generator cannot be serialized.",
+            )),
+        }
+    }
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Set the id generator of the tree to a built-in id generator.
+    ///
+    /// This method replaces the id generator used by `Tree::add_value`. The state of the generator is
+    /// serialized along with the tree, and clones of the tree get their own copy of the generator.
+    ///
+    /// A custom generator set with [`Tree::set_custom_id_generator`] behaves differently: clones of the
+    /// tree share it, and the tree cannot be serialized while it uses one. Setting a built-in generator
+    /// again makes the tree serializable.
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The built-in id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{PrefixedIds, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<String, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.set_id_generator(PrefixedIds::new("node-"));
+    /// let root = tree.add_value(Some(1), None)?;
+    /// assert_eq!(root, "node-1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    pub fn set_id_generator(&mut self, generator: impl Into<BuiltinIdGenerator>) {
+        self.id_generator = Some(TreeIdGenerator::Builtin(generator.into()));
+    }
+
+    /// Set the id generator of the tree to a custom id generator.
+    ///
+    /// This method replaces the id generator used by `Tree::add_value`. Clones of the tree, deep
+    /// clones included, share the generator, so an id generated for one of them is not generated for
+    /// the others. The generator cannot be serialized, so serializing the tree returns an error until
+    /// a built-in generator is set with [`Tree::set_id_generator`].
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The custom id generator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IdGenerator, Tree};
+    ///
+    /// struct EvenIds(i32);
+    ///
+    /// impl IdGenerator<i32> for EvenIds {
+    ///     fn next_id(&mut self) -> tree_ds::prelude::Result<i32> {
+    ///         self.0 += 2;
+    ///         Ok(self.0)
+    ///     }
+    ///
+    ///     fn reset(&mut self) {
+    ///         self.0 = 0;
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// tree.set_custom_id_generator(EvenIds(0));
+    /// let root = tree.add_value(Some(1), None)?;
+    /// assert_eq!(tree.add_value(Some(2), Some(&root))?, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    pub fn set_custom_id_generator<G>(&mut self, generator: G)
+    where
+        G: IdGenerator<Q> + 'static,
+    {
+        let generator: Box<dyn IdGenerator<Q>> = Box::new(generator);
+        self.id_generator = Some(TreeIdGenerator::Custom(Shared::new(Lock::new(generator))));
+    }
+
+    /// Reset the id generator of the tree.
+    ///
+    /// This method resets the id generator used by `Tree::add_value` so that it generates the same ids
+    /// again. Ids that are already used by nodes of the tree are skipped by `Tree::add_value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Tree, NodeRemovalStrategy};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let root = tree.add_value(Some(1), None)?;
+    /// tree.remove_node(&root, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+    /// tree.reset_id_generator();
+    /// assert_eq!(tree.add_value(Some(2), None)?, root);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    pub fn reset_id_generator(&mut self)
+    where
+        Q: GeneratedId,
+    {
+        match self.id_generator.as_mut() {
+            Some(TreeIdGenerator::Builtin(generator)) => IdGenerator::<Q>::reset(generator),
+            Some(TreeIdGenerator::Custom(generator)) => generator.borrow_mut().reset(),
+            None => {}
+        }
+    }
+
+    /// Add a node with a generated id to the tree.
+    ///
+    /// This method adds a node with the given value to the tree like `Tree::add_node`, allocating its id
+    /// from the id generator of the tree. Generated ids that are already used by nodes of the tree are
+    /// skipped. If no id generator was set, the tree uses a `SequentialIds` generator starting from 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the node.
+    /// * `parent_id` - The id of the parent node. If `None`, the node is added as a root node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the node that was added to the tree. An error is returned if the id generator fails, if
+    /// it keeps generating ids that are already used by the tree, or if the node cannot be added to the
+    /// tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Sample Tree"));
+    /// let root = tree.add_value(Some("Harry Doe"), None)?;
+    /// let child = tree.add_value(Some("Jane Doe"), Some(&root))?;
+    /// assert_eq!((root, child), (1, 2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// This is available only when the `auto_id` feature is enabled.
+    pub fn add_value(&mut self, value: Option<T>, parent_id: Option<&Q>) -> Result<Q>
+    where
+        Q: GeneratedId,
+    {
+        let generator = self
+            .id_generator
+            .get_or_insert_with(|| TreeIdGenerator::Builtin(SequentialIds::default().into()));
+        // A generator that never repeats an id finds a free one within one more attempt than there are nodes.
+        for _ in 0..=self.arena.len() {
+            let node_id = match generator {
+                TreeIdGenerator::Builtin(generator) => generator.next_id()?,
+                TreeIdGenerator::Custom(generator) => generator.borrow_mut().next_id()?,
+            };
+            if !self.arena.contains(&node_id) {
+                return self.add_node(Node::new(node_id, value), parent_id);
+            }
+        }
+        Err(InvalidOperation(String::from(
+            "The id generator did not generate an id that is not used by the tree.",
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::NodeRemovalStrategy;
+
+    #[test]
+    fn test_sequential_ids() -> Result<()> {
+        let mut ids = SequentialIds::new(i8::MAX as u128 - 1);
+        assert_eq!(IdGenerator::<i8>::next_id(&mut ids)?, 126);
+        assert_eq!(IdGenerator::<i8>::next_id(&mut ids)?, 127);
+        // The ids do not wrap around once they no longer fit in the id type.
+        assert!(IdGenerator::<i8>::next_id(&mut ids).is_err());
+        assert_eq!(IdGenerator::<i16>::next_id(&mut ids)?, 128);
+        IdGenerator::<i8>::reset(&mut ids);
+        assert_eq!(IdGenerator::<i8>::next_id(&mut ids)?, 126);
+
+        let mut ids = SequentialIds::new(u128::MAX);
+        assert!(IdGenerator::<u128>::next_id(&mut ids).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_random_ids() -> Result<()> {
+        let mut ids = RandomIds::with_seed(7);
+        let generated = (0..1000)
+            .map(|_| ids.next_id())
+            .collect::<Result<Vec<u128>>>()?;
+        let mut unique = generated.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), generated.len());
+        IdGenerator::<u128>::reset(&mut ids);
+        assert_eq!(IdGenerator::<u128>::next_id(&mut ids)?, generated[0]);
+        assert_eq!(
+            IdGenerator::<String>::next_id(&mut ids)?,
+            generated[1].to_string()
+        );
+        assert!(IdGenerator::<u32>::next_id(&mut ids).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_prefixed_ids() -> Result<()> {
+        let mut ids = PrefixedIds::starting_at("node-", 5);
+        assert_eq!(IdGenerator::<String>::next_id(&mut ids)?, "node-5");
+        assert_eq!(IdGenerator::<String>::next_id(&mut ids)?, "node-6");
+        assert!(IdGenerator::<i32>::next_id(&mut ids).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_add_value() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let root = tree.add_value(Some(1), None)?;
+        let child = tree.add_value(Some(2), Some(&root))?;
+        assert_eq!((root, child), (1, 2));
+        // Every tree has its own generator.
+        let mut other_tree: Tree<i32, i32> = Tree::new(Some("Other Tree"));
+        assert_eq!(other_tree.add_value(Some(1), None)?, 1);
+
+        // Ids that are already used are skipped.
+        tree.add_node(Node::new(3, Some(3)), Some(&root))?;
+        assert_eq!(tree.add_value(Some(4), Some(&root))?, 4);
+        tree.remove_node(&child, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        tree.reset_id_generator();
+        assert_eq!(tree.add_value(Some(5), Some(&root))?, 2);
+        assert_eq!(tree.add_value(Some(6), Some(&root))?, 5);
+        tree.debug_assert_valid();
+        assert!(tree.add_value(Some(7), Some(&10)).is_err());
+        assert!(tree.add_value(Some(7), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_custom_id_generator() -> Result<()> {
+        struct Countdown(i32);
+
+        impl IdGenerator<i32> for Countdown {
+            fn next_id(&mut self) -> Result<i32> {
+                self.0 -= 1;
+                Ok(self.0)
+            }
+
+            fn reset(&mut self) {
+                self.0 = 0;
+            }
+        }
+
+        let mut tree = Tree::new(Some("Sample Tree"));
+        tree.set_custom_id_generator(Countdown(0));
+        let root = tree.add_value(Some(1), None)?;
+        // Clones of the tree share the generator.
+        let mut clone = tree.clone();
+        assert_eq!(clone.add_value(Some(2), Some(&root))?, -2);
+        assert_eq!(tree.add_value(Some(3), Some(&root))?, -3);
+
+        // A generator that only repeats ids of the tree is given up on.
+        struct Constant(i32);
+
+        impl IdGenerator<i32> for Constant {
+            fn next_id(&mut self) -> Result<i32> {
+                Ok(self.0)
+            }
+
+            fn reset(&mut self) {}
+        }
+
+        tree.set_custom_id_generator(Constant(root));
+        assert!(matches!(
+            tree.add_value(Some(4), Some(&root)),
+            Err(InvalidOperation(_))
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tree_id_generator_serde() -> Result<()> {
+        let mut tree: Tree<String, i32> = Tree::new(Some("Sample Tree"));
+        tree.set_id_generator(PrefixedIds::new("node-"));
+        let root = tree.add_value(Some(1), None)?;
+        tree.add_value(Some(2), Some(&root))?;
+
+        let serialized = serde_json::to_string(&tree).unwrap();
+        let mut deserialized: Tree<String, i32> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, tree);
+        assert_eq!(deserialized.add_value(Some(3), Some(&root))?, "node-3");
+        assert_eq!(tree.add_value(Some(3), Some(&root))?, "node-3");
+
+        // Custom generators cannot be serialized.
+        tree.set_custom_id_generator(PrefixedIds::new("other-"));
+        assert!(serde_json::to_string(&tree).is_err());
+        tree.set_id_generator(PrefixedIds::new("node-"));
+        assert!(serde_json::to_string(&tree).is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use crate::error::Error::ParseError;
+use crate::lib::*;
+use crate::prelude::{Node, Result, Tree};
+
+/// The characters drawing the branches in front of a node in the output of `Display`.
+const BRANCH_CHARS: [char; 5] = [' ', '│', '├', '└', '─'];
+
+/// The width of one level of indentation in the output of `Display`.
+const LEVEL_WIDTH: usize = 4;
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Read a tree from the output of its `Display` implementation.
+    ///
+    /// This method reads the diagram printed by `Display`: an optional name followed by a line of `*`,
+    /// then one `id: value` line per node with the branches drawn in front of it. The depth of a node
+    /// is given by the width of the branches, four characters per level. The ids and values are parsed
+    /// with `FromStr`, so every node gets a value. A diagram with more than one root node is read into a
+    /// forest.
+    ///
+    /// The diagram can be indented as a whole, which allows writing it as an indented string in the
+    /// source code. Blank lines are skipped, except in the header: the header of a tree named with an
+    /// empty string is two empty lines, so a diagram starting with two empty lines is read as a tree
+    /// with an empty name.
+    ///
+    /// # Arguments
+    ///
+    /// * `diagram` - The output of `Display`.
+    ///
+    /// # Returns
+    ///
+    /// The tree. An error is returned if:
+    /// - A line is not an `id: value` line.
+    /// - A node is indented more than one level deeper than the node above it.
+    /// - An id or a value cannot be parsed.
+    /// - Two nodes have the same id.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let tree: Tree<i32, i32> = Tree::from_display_str(
+    ///     "
+    ///     Sample Tree
+    ///     ***********
+    ///     1: 2
+    ///     └── 2: 3
+    ///         ├── 3: 6
+    ///         │   └── 5: 6
+    ///         └── 4: 5
+    ///     ",
+    /// )?;
+    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
+    /// assert_eq!(tree.get_node_by_id(&5).unwrap().get_parent_id(), Some(3));
+    /// assert_eq!(tree.get_node_by_id(&2).unwrap().get_children_ids(), vec![3, 4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_display_str(diagram: &str) -> Result<Self>
+    where
+        Q: FromStr,
+        T: FromStr,
+    {
+        // The header is read before the blank lines are skipped, since a blank name is written as a
+        // blank line.
+        let rows = diagram.lines().collect::<Vec<_>>();
+        let first = rows
+            .iter()
+            .position(|line| !line.trim().is_empty())
+            .unwrap_or(rows.len());
+        let header = [Some(first), first.checked_sub(1), first.checked_sub(2)]
+            .into_iter()
+            .flatten()
+            .find_map(|row| Some((row, header_name(rows.get(row)?, rows.get(row + 1)?)?)));
+        let (name, skipped) = match header {
+            Some((row, name)) => (Some(name), row + 2),
+            None => (None, 0),
+        };
+
+        let mut lines = rows
+            .into_iter()
+            .enumerate()
+            .skip(skipped)
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+        let indentation = lines.peek().map_or("", |(_, line)| {
+            &line[..line.len() - line.trim_start().len()]
+        });
+        let lines = lines
+            .map(|(number, line)| {
+                line.strip_prefix(indentation)
+                    .map(|line| (number + 1, line.trim_end()))
+                    .ok_or_else(|| {
+                        ParseError(format!(
+                            "Line {} is indented less than the first line.",
+                            number + 1
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut nodes = vec![];
+        for (number, line) in lines {
+            let rest = line.trim_start_matches(BRANCH_CHARS);
+            let width = line[..line.len() - rest.len()].chars().count();
+            if width % LEVEL_WIDTH != 0 {
+                return Err(ParseError(format!(
+                    "Line {} is not aligned on a level of the tree.",
+                    number
+                )));
+            }
+            let (node_id, value) = rest
+                .split_once(": ")
+                .or_else(|| rest.strip_suffix(':').map(|node_id| (node_id, "")))
+                .ok_or_else(|| ParseError(format!("Expected \"id: value\" on line {}.", number)))?;
+            let node_id = node_id.parse::<Q>().map_err(|_| {
+                ParseError(format!(
+                    "Invalid node id \"{}\" on line {}.",
+                    node_id, number
+                ))
+            })?;
+            let value = value.parse::<T>().map_err(|_| {
+                ParseError(format!("Invalid value \"{}\" on line {}.", value, number))
+            })?;
+            nodes.push((number, width / LEVEL_WIDTH, node_id, value));
+        }
+
+        let forest = nodes.iter().filter(|(_, depth, _, _)| *depth == 0).count() > 1;
+        let mut tree = if forest {
+            Tree::new_forest(name)
+        } else {
+            Tree::new(name)
+        };
+        // The ids of the last node read at every depth, from the root node down.
+        let mut path: Vec<Q> = vec![];
+        for (number, depth, node_id, value) in nodes {
+            if depth > path.len() {
+                return Err(ParseError(format!(
+                    "Line {} is indented more than one level below its parent.",
+                    number
+                )));
+            }
+            path.truncate(depth);
+            tree.add_node(Node::new(node_id.clone(), Some(value)), path.last())?;
+            path.push(node_id);
+        }
+        Ok(tree)
+    }
+}
+
+/// Read the name of a tree from the line holding it and the line of `*` below it.
+///
+/// The name is taken from the line as it is, or without its trailing whitespace, whichever has as many
+/// characters as the line of `*`.
+fn header_name<'a>(name: &'a str, stars: &str) -> Option<&'a str> {
+    let rest = stars.trim_start();
+    let name = name.strip_prefix(&stars[..stars.len() - rest.len()])?;
+    let stars = rest.trim_end();
+    if !stars.chars().all(|c| c == '*') {
+        return None;
+    }
+    [name, name.trim_end()]
+        .into_iter()
+        .find(|name| name.chars().count() == stars.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error::DuplicateNodeId;
+
+    #[test]
+    fn test_from_display_str() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+        tree.add_node(Node::new(5, Some(6)), Some(&node_3))?;
+        tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+        tree.add_node(Node::new(6, Some(7)), Some(&node_1))?;
+        let parsed = Tree::from_display_str(&tree.to_string())?;
+        parsed.debug_assert_valid();
+        assert_eq!(parsed, tree);
+
+        let unnamed: Tree<String, String> = Tree::from_display_str("a: x\n├── b:\n└── c: y z\n")?;
+        assert_eq!(unnamed.get_name(), None);
+        assert_eq!(
+            unnamed
+                .get_node_by_id(&String::from("b"))
+                .unwrap()
+                .get_value(),
+            Some(String::new())
+        );
+        assert_eq!(
+            unnamed
+                .get_node_by_id(&String::from("c"))
+                .unwrap()
+                .get_value(),
+            Some(String::from("y z"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_display_str_forest() -> Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        let node_1 = forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let node_3 = forest.add_node(Node::new(3, Some(6)), None)?;
+        let node_4 = forest.add_node(Node::new(4, Some(5)), Some(&node_3))?;
+        forest.add_node(Node::new(5, Some(6)), Some(&node_4))?;
+        let parsed = Tree::from_display_str(&forest.to_string())?;
+        assert!(parsed.is_forest());
+        assert_eq!(parsed, forest);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_display_str_blank_names() -> Result<()> {
+        for name in ["", "  ", " Sample Tree "] {
+            let mut tree = Tree::new(Some(name));
+            let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+            tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+            let parsed = Tree::<i32, i32>::from_display_str(&tree.to_string())?;
+            assert_eq!(parsed.get_name(), Some(name));
+            assert_eq!(parsed, tree);
+        }
+        let unnamed = Tree::<i32, i32>::from_display_str("\n    1: 2\n    └── 2: 3\n")?;
+        assert_eq!(unnamed.get_name(), None);
+        assert_eq!(unnamed.get_nodes().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_display_str_errors() {
+        let parse = Tree::<i32, i32>::from_display_str;
+        assert!(matches!(
+            parse("1: 2\n        └── 2: 3\n"),
+            Err(ParseError(_))
+        ));
+        assert!(matches!(parse("1: 2\n└─ 2: 3\n"), Err(ParseError(_))));
+        assert!(matches!(parse("1: 2\n└── 2 3\n"), Err(ParseError(_))));
+        assert!(matches!(parse("1: 2\n└── x: 3\n"), Err(ParseError(_))));
+        assert!(matches!(parse("1: 2\n└── 2: x\n"), Err(ParseError(_))));
+        assert!(matches!(parse("  1: 2\n└── 2: 3\n"), Err(ParseError(_))));
+        assert!(matches!(
+            parse("1: 2\n└── 1: 3\n"),
+            Err(DuplicateNodeId(node_id)) if node_id == "1"
+        ));
+    }
+}
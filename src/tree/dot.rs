@@ -0,0 +1,790 @@
+use crate::error::Error::{CycleDetected, DuplicateNodeId, MultipleParents, ParseError};
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::{Node, Result, Tree};
+use crate::tree::{IdMap, IdSet};
+
+/// The direction in which the ranks of a DOT graph are laid out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RankDirection {
+    /// The root is at the top and the leaves at the bottom.
+    #[default]
+    TopToBottom,
+    /// The root is at the bottom and the leaves at the top.
+    BottomToTop,
+    /// The root is on the left and the leaves on the right.
+    LeftToRight,
+    /// The root is on the right and the leaves on the left.
+    RightToLeft,
+}
+
+impl RankDirection {
+    /// The value of the `rankdir` attribute for the direction.
+    #[doc(hidden)]
+    fn as_str(&self) -> &'static str {
+        match self {
+            RankDirection::TopToBottom => "TB",
+            RankDirection::BottomToTop => "BT",
+            RankDirection::LeftToRight => "LR",
+            RankDirection::RightToLeft => "RL",
+        }
+    }
+}
+
+/// A function building a string from a node.
+type NodeLabel<'a, Q, T> = Box<dyn Fn(&Node<Q, T>) -> String + 'a>;
+
+/// A function building the attributes of a node.
+type NodeAttributes<'a, Q, T> = Box<dyn Fn(&Node<Q, T>) -> Vec<(String, String)> + 'a>;
+
+/// The options of `Tree::to_dot`.
+///
+/// By default the nodes are labelled with their ids and the graph is laid out from top to bottom.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{DotOptions, Node, RankDirection};
+///
+/// let options = DotOptions::new()
+///     .with_rank_direction(RankDirection::LeftToRight)
+///     .with_node_label(|node: &Node<i32, i32>| format!("{:?}", node.get_value()))
+///     .with_node_attributes(|node: &Node<i32, i32>| {
+///         vec![(String::from("shape"), String::from("box"))]
+///     });
+/// ```
+pub struct DotOptions<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    rank_direction: RankDirection,
+    node_label: Option<NodeLabel<'a, Q, T>>,
+    node_attributes: Option<NodeAttributes<'a, Q, T>>,
+}
+
+impl<'a, Q, T> DotOptions<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create the default options.
+    ///
+    /// # Returns
+    ///
+    /// The options labelling the nodes with their ids and laying out the graph from top to bottom.
+    pub fn new() -> Self {
+        Self {
+            rank_direction: RankDirection::default(),
+            node_label: None,
+            node_attributes: None,
+        }
+    }
+
+    /// Set the direction in which the graph is laid out.
+    ///
+    /// # Arguments
+    ///
+    /// * `rank_direction` - The direction of the layout.
+    ///
+    /// # Returns
+    ///
+    /// The updated options.
+    pub fn with_rank_direction(mut self, rank_direction: RankDirection) -> Self {
+        self.rank_direction = rank_direction;
+        self
+    }
+
+    /// Set the function building the labels of the nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_label` - The function building the label of a node.
+    ///
+    /// # Returns
+    ///
+    /// The updated options.
+    pub fn with_node_label<F>(mut self, node_label: F) -> Self
+    where
+        F: Fn(&Node<Q, T>) -> String + 'a,
+    {
+        self.node_label = Some(Box::new(node_label));
+        self
+    }
+
+    /// Set the function building the attributes of the nodes, such as `color` or `shape`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_attributes` - The function building the attribute names and values of a node.
+    ///
+    /// # Returns
+    ///
+    /// The updated options.
+    pub fn with_node_attributes<F>(mut self, node_attributes: F) -> Self
+    where
+        F: Fn(&Node<Q, T>) -> Vec<(String, String)> + 'a,
+    {
+        self.node_attributes = Some(Box::new(node_attributes));
+        self
+    }
+}
+
+impl<Q, T> Default for DotOptions<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote a DOT id.
+#[doc(hidden)]
+fn quote(id: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in id.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Write a DOT attribute name, quoting it unless it is a plain identifier.
+#[doc(hidden)]
+fn attribute_name(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_plain = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_plain {
+        name.to_string()
+    } else {
+        quote(name)
+    }
+}
+
+/// A token of a DOT document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    /// An unquoted id or keyword.
+    Word(String),
+    /// A quoted id.
+    Quoted(String),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Arrow,
+}
+
+/// Split a DOT document into tokens.
+#[doc(hidden)]
+fn tokenize(dot: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = dot.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '#' => {
+                // Preprocessor output lines are ignored.
+                chars.by_ref().find(|c| *c == '\n');
+                continue;
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|c| *c == '\n');
+                continue;
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                if !chars.by_ref().any(|c| {
+                    let end = previous == '*' && c == '/';
+                    previous = c;
+                    end
+                }) {
+                    return Err(ParseError(String::from("Unterminated comment.")));
+                }
+                continue;
+            }
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '=' => Token::Equals,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            '-' if chars.peek() == Some(&'>') => {
+                chars.next();
+                Token::Arrow
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                return Err(ParseError(String::from(
+                    "Undirected edges are not supported.",
+                )));
+            }
+            '"' => {
+                let mut id = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('"') => id.push('"'),
+                            Some('\\') => id.push('\\'),
+                            Some('n') => id.push('\n'),
+                            Some('\n') => {}
+                            Some(c) => {
+                                id.push('\\');
+                                id.push(c);
+                            }
+                            None => {
+                                return Err(ParseError(String::from("Unterminated string.")));
+                            }
+                        },
+                        Some(c) => id.push(c),
+                        None => {
+                            return Err(ParseError(String::from("Unterminated string.")));
+                        }
+                    }
+                }
+                Token::Quoted(id)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = String::from(c);
+                while let Some(c) = chars
+                    .peek()
+                    .filter(|c| c.is_alphanumeric() || **c == '_' || **c == '.')
+                {
+                    id.push(*c);
+                    chars.next();
+                }
+                Token::Word(id)
+            }
+            c => {
+                return Err(ParseError(format!("Unexpected character '{}'.", c)));
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// A DOT digraph as read from a document, before it is checked to be a tree.
+#[derive(Default)]
+struct Digraph {
+    name: Option<String>,
+    /// Whether the graph is `strict`, merging repeated edges into one.
+    strict: bool,
+    /// The ids and attributes of the nodes, in the order in which they first appear.
+    nodes: Vec<(String, Vec<(String, String)>)>,
+    positions: IdMap<String, usize>,
+    edges: Vec<(String, String)>,
+}
+
+impl Digraph {
+    /// Get the attributes of a node, adding the node if it was not seen yet.
+    fn node(&mut self, id: String) -> &mut Vec<(String, String)> {
+        let position = match self.positions.get(&id) {
+            Some(position) => *position,
+            None => {
+                self.positions.insert(id.clone(), self.nodes.len());
+                self.nodes.push((id, vec![]));
+                self.nodes.len() - 1
+            }
+        };
+        &mut self.nodes[position].1
+    }
+}
+
+/// A parser of the subset of the DOT language describing trees: a `digraph` with node, edge and
+/// attribute statements. Subgraphs, ports and HTML ids are not supported.
+struct DotParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl DotParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn is_keyword(token: Option<&Token>, keyword: &str) -> bool {
+        matches!(token, Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            token => Err(ParseError(format!(
+                "Expected {:?} but found {:?}.",
+                expected, token
+            ))),
+        }
+    }
+
+    fn id(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(id)) | Some(Token::Quoted(id)) => Ok(id),
+            token => Err(ParseError(format!("Expected an id but found {:?}.", token))),
+        }
+    }
+
+    /// Parse attribute lists, like `[color=red, shape=box][label="A"]`.
+    fn attributes(&mut self) -> Result<Vec<(String, String)>> {
+        let mut attributes = vec![];
+        while self.peek() == Some(&Token::LeftBracket) {
+            self.next();
+            while self.peek() != Some(&Token::RightBracket) {
+                let name = self.id()?;
+                self.expect(Token::Equals)?;
+                attributes.push((name, self.id()?));
+                if matches!(self.peek(), Some(Token::Comma) | Some(Token::Semicolon)) {
+                    self.next();
+                }
+            }
+            self.expect(Token::RightBracket)?;
+        }
+        Ok(attributes)
+    }
+
+    fn parse(mut self) -> Result<Digraph> {
+        let mut graph = Digraph::default();
+        if Self::is_keyword(self.peek(), "strict") {
+            self.next();
+            graph.strict = true;
+        }
+        if !Self::is_keyword(self.peek(), "digraph") {
+            return Err(ParseError(String::from(
+                "Only directed graphs starting with `digraph` are supported.",
+            )));
+        }
+        self.next();
+        if self.peek() != Some(&Token::LeftBrace) {
+            graph.name = Some(self.id()?);
+        }
+        self.expect(Token::LeftBrace)?;
+        while self.peek() != Some(&Token::RightBrace) {
+            let token = self.peek();
+            if ["graph", "node", "edge"]
+                .iter()
+                .any(|keyword| Self::is_keyword(token, keyword))
+            {
+                // Default attributes do not change the shape of the tree.
+                self.next();
+                self.attributes()?;
+            } else if Self::is_keyword(token, "subgraph") || token == Some(&Token::LeftBrace) {
+                return Err(ParseError(String::from("Subgraphs are not supported.")));
+            } else {
+                let id = self.id()?;
+                match self.peek() {
+                    Some(Token::Equals) => {
+                        // Graph attributes do not change the shape of the tree.
+                        self.next();
+                        self.id()?;
+                    }
+                    Some(Token::Arrow) => {
+                        graph.node(id.clone());
+                        let mut parent = id;
+                        while self.peek() == Some(&Token::Arrow) {
+                            self.next();
+                            let child = self.id()?;
+                            graph.node(child.clone());
+                            graph.edges.push((parent, child.clone()));
+                            parent = child;
+                        }
+                        self.attributes()?;
+                    }
+                    _ => {
+                        let attributes = self.attributes()?;
+                        graph.node(id).extend(attributes);
+                    }
+                }
+            }
+            if self.peek() == Some(&Token::Semicolon) {
+                self.next();
+            }
+        }
+        self.expect(Token::RightBrace)?;
+        if let Some(token) = self.peek() {
+            return Err(ParseError(format!(
+                "Unexpected {:?} after the graph.",
+                token
+            )));
+        }
+        Ok(graph)
+    }
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Export the tree as a Graphviz DOT digraph.
+    ///
+    /// This method writes a `digraph` named after the tree with a statement for every node, followed
+    /// by an edge from every node to each of its children. The options set the
+    /// labels and attributes of the nodes and the direction of the layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options of the export.
+    ///
+    /// # Returns
+    ///
+    /// The DOT document.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{DotOptions, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let options = DotOptions::new().with_node_label(|node: &Node<i32, i32>| {
+    ///     format!("{}: {}", node.get_node_id(), node.get_value().unwrap_or_default())
+    /// });
+    /// let dot = tree.to_dot(&options)?;
+    /// assert!(dot.contains("\"1\" [label=\"1: 2\"];"));
+    /// assert!(dot.contains("\"1\" -> \"2\";"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_dot(&self, options: &DotOptions<'_, Q, T>) -> Result<String> {
+        let mut dot = String::new();
+        match &self.name {
+            Some(name) => writeln!(dot, "digraph {} {{", quote(name))?,
+            None => writeln!(dot, "digraph {{")?,
+        }
+        writeln!(dot, "    rankdir={};", options.rank_direction.as_str())?;
+        for node in self.arena.nodes().iter() {
+            let label = match &options.node_label {
+                Some(node_label) => node_label(node),
+                None => node.get_node_id().to_string(),
+            };
+            write!(
+                dot,
+                "    {} [label={}",
+                quote(&node.get_node_id().to_string()),
+                quote(&label)
+            )?;
+            if let Some(node_attributes) = &options.node_attributes {
+                for (name, value) in node_attributes(node) {
+                    write!(dot, ", {}={}", attribute_name(&name), quote(&value))?;
+                }
+            }
+            writeln!(dot, "];")?;
+        }
+        for handle in self.arena.handles() {
+            let Some(node_id) = self.id_of(handle) else {
+                continue;
+            };
+            let node_id = quote(&node_id.to_string());
+            for child_id in self.children_ids(handle) {
+                writeln!(dot, "    {} -> {};", node_id, quote(&child_id.to_string()))?;
+            }
+        }
+        writeln!(dot, "}}")?;
+        Ok(dot)
+    }
+
+    /// Read a tree from a Graphviz DOT digraph.
+    ///
+    /// This method reads the nodes and edges of a `digraph` into a tree named after the graph. The node
+    /// ids are parsed with `FromStr`, and the value of every node is built by the given function from
+    /// the id and the attributes of the node. The children of a node keep the order of its edges. A
+    /// graph with more than one root node is read into a forest. In a `strict` graph repeated edges
+    /// are read as a single edge.
+    ///
+    /// Only the subset of DOT needed to describe trees is supported: node, edge and attribute
+    /// statements and comments. Subgraphs, ports and HTML ids are rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `dot` - The DOT document.
+    /// * `value` - The function building the value of a node from its id and attributes.
+    ///
+    /// # Returns
+    ///
+    /// The tree. An error is returned if:
+    /// - The document cannot be parsed or a node id cannot be parsed with `FromStr`.
+    /// - Two node ids are parsed into the same id.
+    /// - A node has more than one parent, or the same parent twice outside of a `strict` graph.
+    /// - The graph has a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::Tree;
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let dot = r#"
+    ///     digraph "Sample Tree" {
+    ///         1 [label="Root"];
+    ///         1 -> 2;
+    ///         1 -> 3 -> 4;
+    ///     }
+    /// "#;
+    /// let tree: Tree<i32, String> = Tree::from_dot(dot, |_, attributes| {
+    ///     attributes
+    ///         .iter()
+    ///         .find(|(name, _)| name == "label")
+    ///         .map(|(_, label)| label.clone())
+    /// })?;
+    /// assert_eq!(tree.get_name(), Some("Sample Tree"));
+    /// assert_eq!(tree.get_node_by_id(&1).unwrap().get_value(), Some(String::from("Root")));
+    /// assert_eq!(tree.get_node_by_id(&4).unwrap().get_parent_id(), Some(3));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_dot<F>(dot: &str, mut value: F) -> Result<Self>
+    where
+        Q: FromStr,
+        F: FnMut(&Q, &[(String, String)]) -> Option<T>,
+    {
+        let graph = DotParser {
+            tokens: tokenize(dot)?,
+            position: 0,
+        }
+        .parse()?;
+        let mut ids = IdMap::new();
+        let mut node_ids = IdSet::new();
+        for (id, _) in graph.nodes.iter() {
+            let node_id = id
+                .parse::<Q>()
+                .map_err(|_| ParseError(format!("Invalid node id \"{}\".", id)))?;
+            if !node_ids.insert(node_id.clone()) {
+                return Err(DuplicateNodeId(node_id.to_string()));
+            }
+            ids.insert(id.clone(), node_id);
+        }
+        let mut parents = IdMap::new();
+        let mut children = IdMap::<Q, Vec<Q>>::new();
+        for (parent, child) in graph.edges.iter() {
+            let (parent_id, child_id) = (ids[parent].clone(), ids[child].clone());
+            if parent_id == child_id {
+                return Err(CycleDetected(child_id.to_string()));
+            }
+            match parents.get(&child_id) {
+                // A strict graph merges the repeated edges into one.
+                Some(existing) if graph.strict && *existing == parent_id => continue,
+                Some(_) => return Err(MultipleParents(child_id.to_string())),
+                None => {
+                    parents.insert(child_id.clone(), parent_id.clone());
+                }
+            }
+            children.entry(parent_id).or_default().push(child_id);
+        }
+
+        // Every node has at most one parent, so the nodes that cannot be reached from a root node are
+        // on a cycle or below one.
+        let mut reachable = IdSet::new();
+        let mut stack = graph
+            .nodes
+            .iter()
+            .map(|(id, _)| &ids[id])
+            .filter(|node_id| !parents.contains_key(*node_id))
+            .collect::<Vec<_>>();
+        let roots = stack.len();
+        while let Some(node_id) = stack.pop() {
+            reachable.insert(node_id.clone());
+            stack.extend(children.get(node_id).into_iter().flatten());
+        }
+        if let Some((id, _)) = graph
+            .nodes
+            .iter()
+            .find(|(id, _)| !reachable.contains(&ids[id]))
+        {
+            let mut node_id = &ids[id];
+            let mut visited = IdSet::new();
+            while visited.insert(node_id.clone()) {
+                node_id = &parents[node_id];
+            }
+            return Err(CycleDetected(node_id.to_string()));
+        }
+
+        let nodes = graph
+            .nodes
+            .iter()
+            .map(|(id, attributes)| {
+                let node_id = ids[id].clone();
+                let node = Node::new(node_id.clone(), value(&node_id, attributes));
+                node.set_parent_id(parents.get(&node_id).cloned());
+                node.set_children_ids(children.remove(&node_id).unwrap_or_default());
+                node
+            })
+            .collect();
+        let mut tree = Tree::from_parts(graph.name, Nodes::new(nodes));
+        tree.forest = roots > 1;
+        Ok(tree)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::TraversalStrategy;
+    use crate::tree::tests::sample_tree;
+
+    fn label_value(id: &i32, attributes: &[(String, String)]) -> Option<i32> {
+        attributes
+            .iter()
+            .find(|(name, _)| name == "label")
+            .and_then(|(_, label)| label.strip_prefix(&format!("{}: ", id)))
+            .and_then(|value| value.parse().ok())
+    }
+
+    #[test]
+    fn test_to_dot() -> Result<()> {
+        let tree = sample_tree()?;
+        let options = DotOptions::new()
+            .with_rank_direction(RankDirection::LeftToRight)
+            .with_node_attributes(|node: &Node<i32, i32>| {
+                if node.get_children_ids().is_empty() {
+                    vec![(String::from("shape"), String::from("box"))]
+                } else {
+                    vec![]
+                }
+            });
+        assert_eq!(
+            tree.to_dot(&options)?,
+            "digraph \"Sample Tree\" {\n    rankdir=LR;\n    \"1\" [label=\"1\"];\n    \
+             \"2\" [label=\"2\"];\n    \"3\" [label=\"3\"];\n    \
+             \"4\" [label=\"4\", shape=\"box\"];\n    \"5\" [label=\"5\", shape=\"box\"];\n    \
+             \"6\" [label=\"6\", shape=\"box\"];\n    \"1\" -> \"2\";\n    \"1\" -> \"3\";\n    \
+             \"2\" -> \"4\";\n    \"2\" -> \"5\";\n    \"3\" -> \"6\";\n}\n"
+        );
+        let empty: Tree<i32, i32> = Tree::default();
+        assert_eq!(
+            empty.to_dot(&DotOptions::default())?,
+            "digraph {\n    rankdir=TB;\n}\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dot_round_trip() -> Result<()> {
+        let tree = sample_tree()?;
+        let options = DotOptions::new().with_node_label(|node: &Node<i32, i32>| {
+            format!("{}: {}", node.get_node_id(), node.get_value().unwrap())
+        });
+        let read = Tree::from_dot(&tree.to_dot(&options)?, label_value)?;
+        read.debug_assert_valid();
+        assert_eq!(read, tree);
+
+        let mut forest = Tree::new_forest(Some("Sample \"Forest\""));
+        forest.add_node(Node::new(String::from("a\\b"), Some(1)), None)?;
+        forest.add_node(Node::new(String::from("c"), Some(2)), None)?;
+        let read = Tree::from_dot(&forest.to_dot(&DotOptions::new())?, |_, _| Some(1))?;
+        assert!(read.is_forest());
+        assert_eq!(read.get_name(), Some("Sample \"Forest\""));
+        assert!(read.get_node_by_id(&String::from("a\\b")).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_dot() -> Result<()> {
+        let dot = r#"
+            /* A tree. */
+            strict digraph {
+                graph [fontname="Helvetica"];
+                node [shape=circle]
+                rankdir = LR
+                // The children keep the order of their edges.
+                1 -> 3 [color=red];
+                1 -> 2 -> 4
+                5 [label = "5: 7", color="blue"]
+                2 -> 5
+                # A preprocessor line.
+            }
+        "#;
+        let tree: Tree<i32, i32> = Tree::from_dot(dot, label_value)?;
+        tree.debug_assert_valid();
+        assert_eq!(tree.get_name(), None);
+        assert!(!tree.is_forest());
+        assert_eq!(
+            tree.traverse(TraversalStrategy::PreOrder, &1)?,
+            vec![1, 3, 2, 4, 5]
+        );
+        assert_eq!(tree.get_node_by_id(&5).unwrap().get_value(), Some(7));
+        assert_eq!(tree.get_node_by_id(&4).unwrap().get_value(), None);
+
+        let repeated: Tree<i32, i32> =
+            Tree::from_dot("strict digraph { 1 -> 2; 1 -> 2 -> 3; }", label_value)?;
+        repeated.debug_assert_valid();
+        assert_eq!(
+            repeated.get_node_by_id(&1).unwrap().get_children_ids(),
+            vec![2]
+        );
+
+        let empty: Tree<i32, i32> = Tree::from_dot("digraph Empty {}", label_value)?;
+        assert!(empty.get_nodes().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_dot_errors() {
+        let read = |dot: &str| Tree::<i32, i32>::from_dot(dot, label_value);
+        assert!(matches!(
+            read("digraph { 1 -> 2; 3 -> 2; }"),
+            Err(MultipleParents(node_id)) if node_id == "2"
+        ));
+        assert!(matches!(
+            read("digraph { 1 -> 2; 1 -> 2; }"),
+            Err(MultipleParents(node_id)) if node_id == "2"
+        ));
+        assert!(matches!(
+            read("strict digraph { 1 -> 2; 3 -> 2; }"),
+            Err(MultipleParents(node_id)) if node_id == "2"
+        ));
+        assert!(matches!(
+            read("digraph { 1 -> 2 -> 3 -> 2; }"),
+            Err(MultipleParents(node_id)) if node_id == "2"
+        ));
+        assert!(matches!(
+            read("digraph { 1 -> 2; 3 -> 4 -> 5 -> 3; }"),
+            Err(CycleDetected(node_id)) if node_id == "3" || node_id == "4" || node_id == "5"
+        ));
+        assert!(matches!(
+            read("digraph { 1 -> 1; }"),
+            Err(CycleDetected(node_id)) if node_id == "1"
+        ));
+        assert!(matches!(
+            read("digraph { 1; 01; }"),
+            Err(DuplicateNodeId(node_id)) if node_id == "1"
+        ));
+        assert!(matches!(read("digraph { a -> 1; }"), Err(ParseError(_))));
+        assert!(matches!(read("graph { 1 -- 2; }"), Err(ParseError(_))));
+        assert!(matches!(read("digraph { 1 -> 2; "), Err(ParseError(_))));
+        assert!(matches!(
+            read("digraph { subgraph { 1 } }"),
+            Err(ParseError(_))
+        ));
+        assert!(matches!(read("digraph { 1 [label] }"), Err(ParseError(_))));
+        assert!(matches!(read("digraph { \"1 }"), Err(ParseError(_))));
+        assert!(matches!(
+            tokenize("\"1\\"),
+            Err(ParseError(message)) if message == "Unterminated string."
+        ));
+        assert!(matches!(read("digraph {} digraph {}"), Err(ParseError(_))));
+    }
+}
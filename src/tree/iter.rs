@@ -0,0 +1,369 @@
+use crate::lib::*;
+use crate::prelude::{Node, NodeHandle, Tree};
+
+/// An iterator over the nodes of a tree that knows the depth of every node it yields.
+///
+/// This trait is implemented by the traversal iterators of the tree. The depth of a node is counted
+/// from the node the traversal started from, which has a depth of 0.
+pub trait TreeIterator: Iterator {
+    /// Get the next node in the traversal together with its depth.
+    fn next_with_depth(&mut self) -> Option<(usize, Self::Item)>;
+
+    /// Turn the iterator into one that yields the depth of every node along with the node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, TreeIterator};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let depths = tree
+    ///     .iter_pre_order(&node_1)?
+    ///     .with_depth()
+    ///     .map(|(depth, node)| (depth, node.get_node_id()))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(depths, vec![(0, node_1), (1, node_2)]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn with_depth(self) -> WithDepth<Self>
+    where
+        Self: Sized,
+    {
+        WithDepth(self)
+    }
+}
+
+/// An iterator that yields the nodes of a traversal together with their depth.
+///
+/// This struct is created by the [`TreeIterator::with_depth`] method.
+#[derive(Clone, Debug)]
+pub struct WithDepth<I>(I);
+
+impl<I> Iterator for WithDepth<I>
+where
+    I: TreeIterator,
+{
+    type Item = (usize, I::Item);
+
+    /// Get the next node in the traversal together with its depth.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_with_depth()
+    }
+}
+
+/// A lazy pre-order iterator over the nodes of a tree.
+///
+/// The iterator visits a node before its children. It keeps an explicit stack of the handles of the
+/// nodes that are still to be visited, so deep trees do not overflow the call stack.
+///
+/// This struct is created by the [`Tree::iter_pre_order`] method.
+#[derive(Clone, Debug)]
+pub struct PreOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    stack: Vec<(usize, NodeHandle)>,
+}
+
+impl<'a, Q, T> PreOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create a pre-order iterator starting from the given node.
+    pub(crate) fn new(tree: &'a Tree<Q, T>, node: NodeHandle) -> Self {
+        Self {
+            tree,
+            stack: vec![(0, node)],
+        }
+    }
+
+    /// Get the handle of the next node in pre-order together with its depth.
+    pub(crate) fn next_handle(&mut self) -> Option<(usize, NodeHandle)> {
+        let (depth, node) = self.stack.pop()?;
+        let children = self.tree.arena.children(node).into_iter().flatten();
+        self.stack
+            .extend(children.rev().map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+
+    /// Turn the iterator into one that yields the handles of the nodes together with their depth.
+    pub(crate) fn handles(mut self) -> impl Iterator<Item = (usize, NodeHandle)> + 'a {
+        core::iter::from_fn(move || self.next_handle())
+    }
+}
+
+impl<Q, T> TreeIterator for PreOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    fn next_with_depth(&mut self) -> Option<(usize, Node<Q, T>)> {
+        let (depth, node) = self.next_handle()?;
+        self.tree
+            .get_node_by_handle(node)
+            .map(|node| (depth, node.clone()))
+    }
+}
+
+impl<Q, T> Iterator for PreOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    type Item = Node<Q, T>;
+
+    /// Get the next node in pre-order.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_depth().map(|(_, node)| node)
+    }
+}
+
+/// A lazy post-order iterator over the nodes of a tree.
+///
+/// The iterator visits a node after all its children. It keeps an explicit stack of the handles of
+/// the nodes that are still to be visited, so deep trees do not overflow the call stack.
+///
+/// This struct is created by the [`Tree::iter_post_order`] method.
+#[derive(Clone, Debug)]
+pub struct PostOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    /// The nodes still to be visited. The flag records whether the children of the node have
+    /// already been pushed onto the stack.
+    stack: Vec<(usize, NodeHandle, bool)>,
+}
+
+impl<'a, Q, T> PostOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create a post-order iterator starting from the given node.
+    pub(crate) fn new(tree: &'a Tree<Q, T>, node: NodeHandle) -> Self {
+        Self {
+            tree,
+            stack: vec![(0, node, false)],
+        }
+    }
+
+    /// Get the handle of the next node in post-order together with its depth.
+    pub(crate) fn next_handle(&mut self) -> Option<(usize, NodeHandle)> {
+        loop {
+            let (depth, node, expanded) = self.stack.pop()?;
+            if expanded {
+                return Some((depth, node));
+            }
+            self.stack.push((depth, node, true));
+            let children = self.tree.arena.children(node).into_iter().flatten();
+            self.stack
+                .extend(children.rev().map(|child| (depth + 1, child, false)));
+        }
+    }
+
+    /// Turn the iterator into one that yields the handles of the nodes together with their depth.
+    pub(crate) fn handles(mut self) -> impl Iterator<Item = (usize, NodeHandle)> + 'a {
+        core::iter::from_fn(move || self.next_handle())
+    }
+}
+
+impl<Q, T> TreeIterator for PostOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    fn next_with_depth(&mut self) -> Option<(usize, Node<Q, T>)> {
+        let (depth, node) = self.next_handle()?;
+        self.tree
+            .get_node_by_handle(node)
+            .map(|node| (depth, node.clone()))
+    }
+}
+
+impl<Q, T> Iterator for PostOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    type Item = Node<Q, T>;
+
+    /// Get the next node in post-order.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_depth().map(|(_, node)| node)
+    }
+}
+
+/// A lazy level-order (breadth-first) iterator over the nodes of a tree.
+///
+/// The iterator visits all the nodes at one depth, from left to right, before moving on to the next
+/// depth. It keeps a queue of the handles of the nodes that are still to be visited.
+///
+/// This struct is created by the [`Tree::iter_level_order`] method.
+#[derive(Clone, Debug)]
+pub struct LevelOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    queue: VecDeque<(usize, NodeHandle)>,
+}
+
+impl<'a, Q, T> LevelOrderIter<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create a level-order iterator starting from the given node.
+    pub(crate) fn new(tree: &'a Tree<Q, T>, node: NodeHandle) -> Self {
+        Self {
+            tree,
+            queue: VecDeque::from(vec![(0, node)]),
+        }
+    }
+
+    /// Get the handle of the next node in level-order together with its depth.
+    pub(crate) fn next_handle(&mut self) -> Option<(usize, NodeHandle)> {
+        let (depth, node) = self.queue.pop_front()?;
+        let children = self.tree.arena.children(node).into_iter().flatten();
+        self.queue.extend(children.map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+
+    /// Turn the iterator into one that yields the handles of the nodes together with their depth.
+    pub(crate) fn handles(mut self) -> impl Iterator<Item = (usize, NodeHandle)> + 'a {
+        core::iter::from_fn(move || self.next_handle())
+    }
+}
+
+impl<Q, T> TreeIterator for LevelOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    fn next_with_depth(&mut self) -> Option<(usize, Node<Q, T>)> {
+        let (depth, node) = self.next_handle()?;
+        self.tree
+            .get_node_by_handle(node)
+            .map(|node| (depth, node.clone()))
+    }
+}
+
+impl<Q, T> Iterator for LevelOrderIter<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    type Item = Node<Q, T>;
+
+    /// Get the next node in level-order.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_depth().map(|(_, node)| node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Result;
+    use crate::tree::tests::sample_tree;
+
+    use super::*;
+
+    #[test]
+    fn test_pre_order_iter() -> Result<()> {
+        let tree = sample_tree()?;
+        let ids = tree
+            .iter_pre_order(&1)?
+            .map(|node| node.get_node_id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 4, 5, 3, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_post_order_iter() -> Result<()> {
+        let tree = sample_tree()?;
+        let ids = tree
+            .iter_post_order(&1)?
+            .map(|node| node.get_node_id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![4, 5, 2, 6, 3, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_level_order_iter() -> Result<()> {
+        let tree = sample_tree()?;
+        let ids = tree
+            .iter_level_order(&1)?
+            .map(|node| node.get_node_id())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_with_depth() -> Result<()> {
+        let tree = sample_tree()?;
+        let depths = tree
+            .iter_post_order(&2)?
+            .with_depth()
+            .map(|(depth, node)| (depth, node.get_node_id()))
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![(1, 4), (1, 5), (0, 2)]);
+        let depths = tree
+            .iter_level_order(&1)?
+            .with_depth()
+            .map(|(depth, _)| depth)
+            .collect::<Vec<_>>();
+        assert_eq!(depths, vec![0, 1, 1, 2, 2, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_stops_early() -> Result<()> {
+        let tree = sample_tree()?;
+        let found = tree
+            .iter_pre_order(&1)?
+            .find(|node| node.get_value() == Some(5));
+        assert_eq!(found.map(|node| node.get_node_id()), Some(4));
+        let prefix = tree
+            .iter_level_order(&1)?
+            .take_while(|node| node.get_node_id() < 4)
+            .count();
+        assert_eq!(prefix, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_iter_deep_tree() -> Result<()> {
+        let mut tree = Tree::new(Some("Deep Tree"));
+        let mut parent = tree.add_node(Node::new(0, Some(0)), None)?;
+        for node_id in 1..100_000 {
+            parent = tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent))?;
+        }
+        assert_eq!(tree.iter_pre_order(&0)?.count(), 100_000);
+        assert_eq!(
+            tree.iter_post_order(&0)?.next().unwrap().get_node_id(),
+            99_999
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_iter_no_existent_node() {
+        let tree = Tree::<u32, u32>::new(Some("Sample Tree"));
+        tree.iter_pre_order(&1).unwrap();
+    }
+}
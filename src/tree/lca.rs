@@ -0,0 +1,484 @@
+use crate::error::Error::{InvalidOperation, NodeNotFound};
+use crate::lib::*;
+use crate::prelude::{Result, Tree};
+use crate::tree::iter::PreOrderIter;
+use crate::tree::IdMap;
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Get the lowest common ancestor of two nodes.
+    ///
+    /// This method gets the deepest node that has both nodes as descendants. A node is considered a
+    /// descendant of itself, so if one node is an ancestor of the other the ancestor is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the first node.
+    /// * `second_id` - The id of the second node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the lowest common ancestor of the two nodes. This method returns an error if either
+    /// node is not found in the tree or if the nodes are in different trees of a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    /// assert_eq!(tree.lowest_common_ancestor(&node_3, &node_4)?, node_2);
+    /// assert_eq!(tree.lowest_common_ancestor(&node_1, &node_4)?, node_1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lowest_common_ancestor(&self, first_id: &Q, second_id: &Q) -> Result<Q> {
+        let (first_chain, _, common) = self.get_common_ancestry(first_id, second_id)?;
+        Ok(first_chain[first_chain.len() - common].clone())
+    }
+
+    /// Get the path between two nodes.
+    ///
+    /// This method gets the ids of the nodes on the path from the first node up to the lowest common
+    /// ancestor of the two nodes and back down to the second node.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the node the path starts at.
+    /// * `second_id` - The id of the node the path ends at.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the nodes on the path, including both ends. This method returns an error if either
+    /// node is not found in the tree or if the nodes are in different trees of a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+    /// assert_eq!(tree.path_between(&node_3, &node_4)?, vec![node_3, node_2, node_1, node_4]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn path_between(&self, first_id: &Q, second_id: &Q) -> Result<Vec<Q>> {
+        let (mut first_chain, second_chain, common) =
+            self.get_common_ancestry(first_id, second_id)?;
+        // Keep the lowest common ancestor on the first half of the path only.
+        first_chain.truncate(first_chain.len() - common + 1);
+        first_chain.extend(second_chain.into_iter().rev().skip(common));
+        Ok(first_chain)
+    }
+
+    /// Get the distance between two nodes.
+    ///
+    /// This method gets the number of edges on the path between two nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the first node.
+    /// * `second_id` - The id of the second node.
+    ///
+    /// # Returns
+    ///
+    /// The distance between the two nodes. This method returns an error if either node is not found in
+    /// the tree or if the nodes are in different trees of a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+    /// assert_eq!(tree.distance(&node_3, &node_4)?, 3);
+    /// assert_eq!(tree.distance(&node_3, &node_3)?, 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn distance(&self, first_id: &Q, second_id: &Q) -> Result<i32> {
+        let (first_chain, second_chain, common) = self.get_common_ancestry(first_id, second_id)?;
+        Ok((first_chain.len() + second_chain.len() - 2 * common) as i32)
+    }
+
+    /// Build an index to answer lowest common ancestor queries in logarithmic time.
+    ///
+    /// This method precomputes the ancestors of every node at power of two distances. The index is a
+    /// snapshot of the tree: it does not see the changes made to the tree after it was built, so it
+    /// should be rebuilt whenever the structure of the tree changes.
+    ///
+    /// # Returns
+    ///
+    /// The index of the tree. This method returns an error if some nodes cannot be reached from a root
+    /// node, which happens when the links of the nodes form a cycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_1))?;
+    ///
+    /// let index = tree.build_lca_index()?;
+    /// assert_eq!(index.lowest_common_ancestor(&node_3, &node_4)?, node_1);
+    /// assert_eq!(index.distance(&node_3, &node_4)?, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_lca_index(&self) -> Result<LcaIndex<Q>> {
+        let mut ids = vec![];
+        let mut depths = vec![];
+        let mut parents = vec![];
+        let mut positions = IdMap::new();
+        let mut handle_positions = IdMap::new();
+        for root in self.root_handles() {
+            for (depth, handle) in PreOrderIter::new(self, root).handles() {
+                let Some(node_id) = self.id_of(handle) else {
+                    continue;
+                };
+                let position = ids.len();
+                // Parents are visited before their children, so the parent already has a position.
+                let parent = self
+                    .arena
+                    .parent(handle)
+                    .and_then(|parent| handle_positions.get(&parent).copied())
+                    .unwrap_or(position);
+                handle_positions.insert(handle, position);
+                positions.insert(node_id.clone(), position);
+                ids.push(node_id);
+                depths.push(depth);
+                parents.push(parent);
+            }
+        }
+        if ids.len() != self.get_nodes().len() {
+            return Err(InvalidOperation(String::from(
+                "The tree has nodes that cannot be reached from a root node.",
+            )));
+        }
+        let mut ancestors = vec![parents];
+        while (1 << ancestors.len()) < ids.len() {
+            let previous = &ancestors[ancestors.len() - 1];
+            let next = previous
+                .iter()
+                .map(|ancestor| previous[*ancestor])
+                .collect();
+            ancestors.push(next);
+        }
+        Ok(LcaIndex {
+            positions,
+            ids,
+            depths,
+            ancestors,
+        })
+    }
+
+    /// Get the ancestry of two nodes.
+    ///
+    /// This method returns the ids of the nodes from each node up to its root node and the number of
+    /// ancestors the two nodes have in common, counting the nodes themselves.
+    #[doc(hidden)]
+    fn get_common_ancestry(&self, first_id: &Q, second_id: &Q) -> Result<(Vec<Q>, Vec<Q>, usize)> {
+        let mut first_chain = vec![first_id.clone()];
+        first_chain.append(&mut self.get_ancestor_ids(first_id)?);
+        let mut second_chain = vec![second_id.clone()];
+        second_chain.append(&mut self.get_ancestor_ids(second_id)?);
+        let common = first_chain
+            .iter()
+            .rev()
+            .zip(second_chain.iter().rev())
+            .take_while(|(first, second)| first == second)
+            .count();
+        if common == 0 {
+            return Err(not_in_same_tree(first_id, second_id));
+        }
+        Ok((first_chain, second_chain, common))
+    }
+}
+
+/// A precomputed index to answer lowest common ancestor queries on a tree in logarithmic time.
+///
+/// The index stores, for every node, its ancestors at a distance of 1, 2, 4, 8 and so on. It is built
+/// with [`Tree::build_lca_index`] and reflects the tree at the time it was built.
+#[derive(Clone, Debug)]
+pub struct LcaIndex<Q> {
+    positions: IdMap<Q, usize>,
+    ids: Vec<Q>,
+    depths: Vec<usize>,
+    /// `ancestors[k][i]` is the position of the ancestor of node `i` at a distance of `2^k`, or the
+    /// position of its root node if the node is not that deep.
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<Q> LcaIndex<Q>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+{
+    /// Get the lowest common ancestor of two nodes.
+    ///
+    /// This method gets the deepest node that has both nodes as descendants in logarithmic time.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the first node.
+    /// * `second_id` - The id of the second node.
+    ///
+    /// # Returns
+    ///
+    /// The id of the lowest common ancestor of the two nodes. This method returns an error if either
+    /// node is not in the index or if the nodes are in different trees of a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// let index = tree.build_lca_index()?;
+    /// assert_eq!(index.lowest_common_ancestor(&node_3, &node_2)?, node_2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lowest_common_ancestor(&self, first_id: &Q, second_id: &Q) -> Result<Q> {
+        let position = self.get_lca_position(first_id, second_id)?;
+        Ok(self.ids[position].clone())
+    }
+
+    /// Get the distance between two nodes.
+    ///
+    /// This method gets the number of edges on the path between two nodes in logarithmic time.
+    ///
+    /// # Arguments
+    ///
+    /// * `first_id` - The id of the first node.
+    /// * `second_id` - The id of the second node.
+    ///
+    /// # Returns
+    ///
+    /// The distance between the two nodes. This method returns an error if either node is not in the
+    /// index or if the nodes are in different trees of a forest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_1))?;
+    ///
+    /// let index = tree.build_lca_index()?;
+    /// assert_eq!(index.distance(&node_2, &node_3)?, 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn distance(&self, first_id: &Q, second_id: &Q) -> Result<i32> {
+        let ancestor = self.get_lca_position(first_id, second_id)?;
+        let first = self.get_position(first_id)?;
+        let second = self.get_position(second_id)?;
+        Ok((self.depths[first] + self.depths[second] - 2 * self.depths[ancestor]) as i32)
+    }
+
+    /// Get the depth of a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the node.
+    ///
+    /// # Returns
+    ///
+    /// The depth of the node. This method returns an error if the node is not in the index.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let index = tree.build_lca_index()?;
+    /// assert_eq!(index.get_node_depth(&node_2)?, 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_node_depth(&self, node_id: &Q) -> Result<i32> {
+        Ok(self.depths[self.get_position(node_id)?] as i32)
+    }
+
+    /// Get the position of a node in the index.
+    #[doc(hidden)]
+    fn get_position(&self, node_id: &Q) -> Result<usize> {
+        self.positions
+            .get(node_id)
+            .copied()
+            .ok_or(NodeNotFound(node_id.to_string()))
+    }
+
+    /// Get the position of the lowest common ancestor of two nodes.
+    #[doc(hidden)]
+    fn get_lca_position(&self, first_id: &Q, second_id: &Q) -> Result<usize> {
+        let mut first = self.get_position(first_id)?;
+        let mut second = self.get_position(second_id)?;
+        if self.depths[first] < self.depths[second] {
+            core::mem::swap(&mut first, &mut second);
+        }
+        // Lift the deeper node to the depth of the other one.
+        let difference = self.depths[first] - self.depths[second];
+        for (level, ancestors) in self.ancestors.iter().enumerate() {
+            if difference & (1 << level) != 0 {
+                first = ancestors[first];
+            }
+        }
+        if first == second {
+            return Ok(first);
+        }
+        // Lift both nodes to just below their lowest common ancestor.
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[first] != ancestors[second] {
+                first = ancestors[first];
+                second = ancestors[second];
+            }
+        }
+        let (first, second) = (self.ancestors[0][first], self.ancestors[0][second]);
+        if first != second {
+            return Err(not_in_same_tree(first_id, second_id));
+        }
+        Ok(first)
+    }
+}
+
+/// The error returned when two nodes of a forest have no common ancestor.
+fn not_in_same_tree<Q: Display>(first_id: &Q, second_id: &Q) -> crate::error::Error {
+    InvalidOperation(format!(
+        "Nodes {} and {} are not in the same tree.",
+        first_id, second_id
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::Node;
+    use crate::tree::tests::sample_tree;
+
+    use super::*;
+
+    #[test]
+    fn test_lowest_common_ancestor() -> Result<()> {
+        let mut tree = sample_tree()?;
+        tree.add_node(Node::new(7, Some(8)), Some(&4))?;
+        assert_eq!(tree.lowest_common_ancestor(&7, &5)?, 2);
+        assert_eq!(tree.lowest_common_ancestor(&7, &6)?, 1);
+        assert_eq!(tree.lowest_common_ancestor(&4, &7)?, 4);
+        assert_eq!(tree.lowest_common_ancestor(&3, &3)?, 3);
+        assert!(matches!(
+            tree.lowest_common_ancestor(&3, &8),
+            Err(NodeNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_between_and_distance() -> Result<()> {
+        let mut tree = sample_tree()?;
+        tree.add_node(Node::new(7, Some(8)), Some(&4))?;
+        assert_eq!(tree.path_between(&7, &6)?, vec![7, 4, 2, 1, 3, 6]);
+        assert_eq!(tree.path_between(&2, &7)?, vec![2, 4, 7]);
+        assert_eq!(tree.path_between(&7, &2)?, vec![7, 4, 2]);
+        assert_eq!(tree.path_between(&5, &5)?, vec![5]);
+        assert_eq!(tree.distance(&7, &6)?, 5);
+        assert_eq!(tree.distance(&7, &5)?, 3);
+        assert_eq!(tree.distance(&1, &1)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lca_index_matches_tree() -> Result<()> {
+        let mut tree = sample_tree()?;
+        tree.add_node(Node::new(7, Some(8)), Some(&4))?;
+        let index = tree.build_lca_index()?;
+        for first in 1..=7 {
+            for second in 1..=7 {
+                assert_eq!(
+                    index.lowest_common_ancestor(&first, &second)?,
+                    tree.lowest_common_ancestor(&first, &second)?
+                );
+                assert_eq!(
+                    index.distance(&first, &second)?,
+                    tree.distance(&first, &second)?
+                );
+            }
+        }
+        assert_eq!(index.get_node_depth(&7)?, 3);
+        assert!(index.lowest_common_ancestor(&1, &8).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lca_index_deep_tree() -> Result<()> {
+        let mut tree = Tree::new(Some("Deep Tree"));
+        let mut parent = tree.add_node(Node::new(0, Some(0)), None)?;
+        for node_id in 1..10_000 {
+            parent = tree.add_node(Node::new(node_id, Some(node_id)), Some(&parent))?;
+        }
+        tree.add_node(Node::new(10_000, Some(0)), Some(&5_000))?;
+        let index = tree.build_lca_index()?;
+        assert_eq!(index.lowest_common_ancestor(&9_999, &10_000)?, 5_000);
+        assert_eq!(index.distance(&9_999, &10_000)?, 5_000);
+        assert_eq!(index.lowest_common_ancestor(&0, &9_999)?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lca_forest() -> Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), Some(&1))?;
+        forest.add_node(Node::new(3, Some(6)), None)?;
+        forest.add_node(Node::new(4, Some(5)), Some(&3))?;
+        assert!(matches!(
+            forest.lowest_common_ancestor(&2, &4),
+            Err(InvalidOperation(_))
+        ));
+        assert!(forest.path_between(&2, &4).is_err());
+        let index = forest.build_lca_index()?;
+        assert!(matches!(
+            index.lowest_common_ancestor(&2, &4),
+            Err(InvalidOperation(_))
+        ));
+        assert!(index.lowest_common_ancestor(&1, &3).is_err());
+        assert_eq!(index.lowest_common_ancestor(&3, &4)?, 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,674 @@
+//! A nested representation of trees for serde.
+//!
+//! By default a tree is serialized as a flat list of nodes holding the ids of their parents and
+//! children. This module serializes a tree as its root node instead, with every node holding its
+//! children:
+//!
+//! ```json
+//! { "id": 1, "value": 2, "children": [{ "id": 2, "value": 3 }] }
+//! ```
+//!
+//! The `value` and `children` fields are left out when the node has no value or no children, and
+//! are optional when deserializing. A forest is serialized as a list of its root nodes and an empty
+//! tree as `null`. The name of the tree is not part of the nested representation.
+//!
+//! Since the shape of the data depends on the tree, the nested representation is read with
+//! `Deserializer::deserialize_any` and only works with self-describing formats such as JSON, YAML,
+//! TOML or MessagePack. Formats that rely on the type to know what comes next, such as `bincode`
+//! or `postcard`, cannot read it back; use the default flat representation with them.
+//!
+//! The module can be used as a serde adapter with `#[serde(with = "tree_ds::nested")]`, or the tree can
+//! be wrapped in a [`Nested`] to serialize it on its own.
+//!
+//! Serde walks nested data with one call per level, so trees deeper than [`MAX_DEPTH`] are rejected
+//! with an error instead of overflowing the stack. The width of the tree is not limited. Formats can
+//! have a lower limit of their own: `serde_json` stops at 128 nested maps and lists, and every level
+//! of nodes is a map holding a list, so it reads nested trees up to 64 levels deep (63 for a forest,
+//! which adds the list of its root nodes). Deeper trees are still written, but reading them back with
+//! `serde_json` requires turning its limit off with `Deserializer::disable_recursion_limit`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # use serde::{Deserialize, Serialize};
+//! # use tree_ds::prelude::{Node, Tree};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "tree_ds::nested")]
+//!     menu: Tree<String, u32>,
+//! }
+//!
+//! let config: Config = serde_json::from_str(
+//!     r#"{"menu": {"id": "file", "children": [{"id": "open", "value": 1}, {"id": "save", "value": 2}]}}"#,
+//! )
+//! .unwrap();
+//! let save = config.menu.get_node_by_id(&String::from("save")).unwrap();
+//! assert_eq!(save.get_parent_id(), Some(String::from("file")));
+//! assert_eq!(save.get_value(), Some(2));
+//! ```
+
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::ser::{self, SerializeSeq, SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
+
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::{Node, NodeHandle, Tree};
+use crate::tree::IdSet;
+
+/// The deepest level of nodes that is serialized or deserialized, the root nodes being at level 1.
+pub const MAX_DEPTH: usize = 256;
+
+/// The fields of a node in the nested representation.
+const FIELDS: &[&str] = &["id", "value", "children"];
+
+/// Serialize a tree in the nested representation.
+///
+/// # Arguments
+///
+/// * `tree` - The tree to serialize.
+/// * `serializer` - The serializer to use.
+///
+/// # Returns
+///
+/// The result of the serializer. An error is returned if the tree has more than one root node without
+/// being a forest, or if it is deeper than [`MAX_DEPTH`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Node, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(None);
+/// let root = tree.add_node(Node::new(1, Some(2)), None)?;
+/// tree.add_node(Node::new(2, None), Some(&root))?;
+/// let mut json = vec![];
+/// tree_ds::nested::serialize(&tree, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// assert_eq!(String::from_utf8(json).unwrap(), r#"{"id":1,"value":2,"children":[{"id":2}]}"#);
+/// # Ok(())
+/// # }
+/// ```
+pub fn serialize<Q, T, S>(tree: &Tree<Q, T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Serialize,
+    T: PartialEq + Eq + Clone + Serialize,
+    S: Serializer,
+{
+    let roots = tree.root_handles().collect::<Vec<_>>();
+    if tree.is_forest() {
+        let mut seq = serializer.serialize_seq(Some(roots.len()))?;
+        for root in roots {
+            seq.serialize_element(&NestedNode {
+                tree,
+                node: root,
+                depth: 1,
+            })?;
+        }
+        return seq.end();
+    }
+    match roots.as_slice() {
+        [] => serializer.serialize_none(),
+        [root] => NestedNode {
+            tree,
+            node: *root,
+            depth: 1,
+        }
+        .serialize(serializer),
+        _ => Err(ser::Error::custom(
+            "A tree with more than one root node must be a forest to be serialized.",
+        )),
+    }
+}
+
+/// Deserialize a tree from the nested representation.
+///
+/// This method reads a single root node into a tree, a list of root nodes into a forest and `null`
+/// into an empty tree. The parent of every node is the node whose children it is listed in. Telling
+/// these apart uses `Deserializer::deserialize_any`, so the format must be self-describing.
+///
+/// # Arguments
+///
+/// * `deserializer` - The deserializer to use.
+///
+/// # Returns
+///
+/// The tree. An error is returned if a node has no id, two nodes have the same id, or the tree is
+/// deeper than [`MAX_DEPTH`].
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::Tree;
+///
+/// let mut deserializer = serde_json::Deserializer::from_str(r#"[{"id": 1}, {"id": 2, "children": [{"id": 3}]}]"#);
+/// let forest: Tree<i32, i32> = tree_ds::nested::deserialize(&mut deserializer).unwrap();
+/// assert!(forest.is_forest());
+/// assert_eq!(forest.get_root_nodes().len(), 2);
+/// assert_eq!(forest.get_node_by_id(&3).unwrap().get_parent_id(), Some(2));
+/// ```
+pub fn deserialize<'de, Q, T, D>(deserializer: D) -> Result<Tree<Q, T>, D::Error>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Deserialize<'de>,
+    T: PartialEq + Eq + Clone + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(TreeVisitor(core::marker::PhantomData))
+}
+
+/// A tree serialized and deserialized in the nested representation.
+///
+/// The tree is written as `null`, a single root node or a list of root nodes, depending on whether
+/// it is empty, a tree or a forest, so it can only be read back from self-describing formats such as
+/// JSON; `bincode` and `postcard` do not work. Trees deeper than [`MAX_DEPTH`] (256) levels cannot
+/// be serialized or deserialized, and formats may have a lower limit of their own, see the
+/// [module documentation](self).
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{Nested, Node, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, i32> = Tree::new(None);
+/// let root = tree.add_node(Node::new(1, Some(2)), None)?;
+/// tree.add_node(Node::new(2, Some(3)), Some(&root))?;
+/// let json = serde_json::to_string(&Nested(tree.clone())).unwrap();
+/// assert_eq!(json, r#"{"id":1,"value":2,"children":[{"id":2,"value":3}]}"#);
+/// let Nested(deserialized) = serde_json::from_str(&json).unwrap();
+/// assert_eq!(deserialized, tree);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Nested<Q, T>(pub Tree<Q, T>)
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone;
+
+impl<Q, T> From<Tree<Q, T>> for Nested<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Wrap a tree.
+    fn from(tree: Tree<Q, T>) -> Self {
+        Nested(tree)
+    }
+}
+
+impl<Q, T> Serialize for Nested<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Serialize,
+    T: PartialEq + Eq + Clone + Serialize,
+{
+    /// Serialize the tree in the nested representation.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, Q, T> Deserialize<'de> for Nested<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Deserialize<'de>,
+    T: PartialEq + Eq + Clone + Deserialize<'de>,
+{
+    /// Deserialize the tree from the nested representation.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer).map(Nested)
+    }
+}
+
+/// A node serialized along with its descendants.
+struct NestedNode<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    node: NodeHandle,
+    depth: usize,
+}
+
+impl<Q, T> Serialize for NestedNode<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Serialize,
+    T: PartialEq + Eq + Clone + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.depth > MAX_DEPTH {
+            return Err(ser::Error::custom(format!(
+                "The tree is deeper than {} levels.",
+                MAX_DEPTH
+            )));
+        }
+        // The handles come from the arena of the tree, so they always resolve.
+        let (Some(node), Some(children)) = (
+            self.tree.arena.get(self.node),
+            self.tree.arena.children(self.node),
+        ) else {
+            return Err(ser::Error::custom("A node is missing from the tree."));
+        };
+        let value = node.get_value();
+        let len = 1 + usize::from(value.is_some()) + usize::from(children.len() > 0);
+        let mut state = serializer.serialize_struct("Node", len)?;
+        state.serialize_field("id", &node.get_node_id())?;
+        match &value {
+            Some(value) => state.serialize_field("value", value)?,
+            None => state.skip_field("value")?,
+        }
+        if children.len() == 0 {
+            state.skip_field("children")?;
+        } else {
+            state.serialize_field(
+                "children",
+                &NestedChildren {
+                    tree: self.tree,
+                    node: self.node,
+                    depth: self.depth + 1,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// The children of a node serialized along with their descendants.
+struct NestedChildren<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    /// The handle of the parent of the children.
+    node: NodeHandle,
+    depth: usize,
+}
+
+impl<Q, T> Serialize for NestedChildren<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Serialize,
+    T: PartialEq + Eq + Clone + Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let children = self
+            .tree
+            .arena
+            .children(self.node)
+            .ok_or_else(|| ser::Error::custom("A node is missing from the tree."))?;
+        let mut seq = serializer.serialize_seq(Some(children.len()))?;
+        for child in children {
+            seq.serialize_element(&NestedNode {
+                tree: self.tree,
+                node: child,
+                depth: self.depth,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+/// A node read from the nested representation. The children are the positions of the child nodes
+/// in the list of read nodes.
+struct NestedEntry<Q, T> {
+    id: Q,
+    value: Option<T>,
+    children: Vec<usize>,
+}
+
+/// The field names of a node.
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Field {
+    Id,
+    Value,
+    Children,
+    #[serde(other)]
+    Other,
+}
+
+/// The visitor of a whole tree.
+struct TreeVisitor<Q, T>(core::marker::PhantomData<(Q, T)>);
+
+impl<'de, Q, T> Visitor<'de> for TreeVisitor<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord + Deserialize<'de>,
+    T: PartialEq + Eq + Clone + Deserialize<'de>,
+{
+    type Value = Tree<Q, T>;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("a node, a list of root nodes or null")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Tree::new(None))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(Tree::new(None))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut entries = vec![];
+        EntryVisitor {
+            entries: &mut entries,
+            depth: 1,
+        }
+        .visit_map(map)?;
+        build(entries, false)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut entries = vec![];
+        while seq
+            .next_element_seed(EntryVisitor {
+                entries: &mut entries,
+                depth: 1,
+            })?
+            .is_some()
+        {}
+        build(entries, true)
+    }
+}
+
+/// The visitor of a node, which adds the node and its descendants to the list of read nodes in
+/// pre-order and returns the position of the node in the list.
+struct EntryVisitor<'a, Q, T> {
+    entries: &'a mut Vec<Option<NestedEntry<Q, T>>>,
+    depth: usize,
+}
+
+impl<'de, Q, T> DeserializeSeed<'de> for EntryVisitor<'_, Q, T>
+where
+    Q: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Node", FIELDS, self)
+    }
+}
+
+impl<'de, Q, T> Visitor<'de> for EntryVisitor<'_, Q, T>
+where
+    Q: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    type Value = usize;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("a node")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        if self.depth > MAX_DEPTH {
+            return Err(de::Error::custom(format!(
+                "The tree is deeper than {} levels.",
+                MAX_DEPTH
+            )));
+        }
+        // The slot of the node is taken before its children are read to keep the nodes in pre-order.
+        let position = self.entries.len();
+        self.entries.push(None);
+        let (mut id, mut value, mut children) = (None, None, None);
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Id if id.is_some() => return Err(de::Error::duplicate_field("id")),
+                Field::Id => id = Some(map.next_value()?),
+                Field::Value if value.is_some() => return Err(de::Error::duplicate_field("value")),
+                Field::Value => value = Some(map.next_value()?),
+                Field::Children if children.is_some() => {
+                    return Err(de::Error::duplicate_field("children"))
+                }
+                Field::Children => {
+                    children = Some(map.next_value_seed(ChildrenVisitor {
+                        entries: &mut *self.entries,
+                        depth: self.depth + 1,
+                    })?)
+                }
+                Field::Other => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        self.entries[position] = Some(NestedEntry {
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            value: value.flatten(),
+            children: children.unwrap_or_default(),
+        });
+        Ok(position)
+    }
+}
+
+/// The visitor of the children of a node, which returns the positions of the children in the list
+/// of read nodes.
+struct ChildrenVisitor<'a, Q, T> {
+    entries: &'a mut Vec<Option<NestedEntry<Q, T>>>,
+    depth: usize,
+}
+
+impl<'de, Q, T> DeserializeSeed<'de> for ChildrenVisitor<'_, Q, T>
+where
+    Q: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    type Value = Vec<usize>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, Q, T> Visitor<'de> for ChildrenVisitor<'_, Q, T>
+where
+    Q: Deserialize<'de>,
+    T: Deserialize<'de>,
+{
+    type Value = Vec<usize>;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str("a list of nodes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut children = vec![];
+        while let Some(child) = seq.next_element_seed(EntryVisitor {
+            entries: &mut *self.entries,
+            depth: self.depth,
+        })? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+/// Build a tree from the read nodes, linking every node to its parent and children.
+fn build<Q, T, E>(entries: Vec<Option<NestedEntry<Q, T>>>, forest: bool) -> Result<Tree<Q, T>, E>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+    E: de::Error,
+{
+    // Every slot is filled once its node is read, and a failed read aborts the deserialization.
+    let entries = entries.into_iter().flatten().collect::<Vec<_>>();
+    let mut parents = vec![None; entries.len()];
+    let mut node_ids = IdSet::new();
+    for (position, entry) in entries.iter().enumerate() {
+        if !node_ids.insert(entry.id.clone()) {
+            return Err(de::Error::custom(format!(
+                "Node {} is already present in the tree.",
+                entry.id
+            )));
+        }
+        for child in entry.children.iter() {
+            parents[*child] = Some(position);
+        }
+    }
+    let nodes = entries
+        .iter()
+        .zip(parents)
+        .map(|(entry, parent)| {
+            let node = Node::new(entry.id.clone(), entry.value.clone());
+            node.set_parent_id(parent.map(|parent| entries[parent].id.clone()));
+            node.set_children_ids(
+                entry
+                    .children
+                    .iter()
+                    .map(|child| entries[*child].id.clone())
+                    .collect(),
+            );
+            node
+        })
+        .collect();
+    let mut tree = Tree::from_parts(None, Nodes::new(nodes));
+    tree.forest = forest;
+    Ok(tree)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::tests::sample_tree;
+
+    #[test]
+    fn test_nested_serialize() {
+        let tree = sample_tree().unwrap();
+        tree.get_node_by_id(&6).unwrap().set_value(None);
+        let json = serde_json::to_string(&Nested(tree)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":1,"value":2,"children":[{"id":2,"value":3,"children":[{"id":4,"value":5},{"id":5,"value":6}]},{"id":3,"value":6,"children":[{"id":6}]}]}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Nested(Tree::<u32, u32>::new(None))).unwrap(),
+            "null"
+        );
+        let mut forest = Tree::new_forest(None);
+        forest.add_node(Node::new(1, Some(2)), None).unwrap();
+        forest.add_node(Node::new(2, None), None).unwrap();
+        assert_eq!(
+            serde_json::to_string(&Nested(forest)).unwrap(),
+            r#"[{"id":1,"value":2},{"id":2}]"#
+        );
+    }
+
+    #[test]
+    fn test_nested_deserialize() {
+        let json = r#"{"children":[{"value":3,"id":2,"children":[{"id":4,"value":5},{"id":5,"value":6,"note":"x"}]},{"id":3,"value":6,"children":[{"id":6,"value":null}]}],"id":1,"value":2}"#;
+        let Nested(tree) = serde_json::from_str::<Nested<i32, i32>>(json).unwrap();
+        tree.debug_assert_valid();
+        let expected = sample_tree().unwrap();
+        expected.get_node_by_id(&6).unwrap().set_value(None);
+        assert_eq!(tree.get_nodes().len(), expected.get_nodes().len());
+        for node in expected.get_nodes().iter() {
+            assert_eq!(
+                tree.get_node_by_id(&node.get_node_id()).as_ref(),
+                Some(node)
+            );
+        }
+        assert_eq!(tree.get_node_by_id(&6).unwrap().get_parent_id(), Some(3));
+
+        let Nested(empty) = serde_json::from_str::<Nested<u32, u32>>("null").unwrap();
+        assert!(empty.get_nodes().is_empty());
+        let Nested(forest) =
+            serde_json::from_str::<Nested<u32, u32>>(r#"[{"id":1},{"id":2}]"#).unwrap();
+        assert!(forest.is_forest());
+        assert_eq!(forest.get_root_nodes().len(), 2);
+
+        let duplicate =
+            serde_json::from_str::<Nested<u32, u32>>(r#"{"id":1,"children":[{"id":1}]}"#);
+        assert!(duplicate
+            .unwrap_err()
+            .to_string()
+            .contains("already present"));
+        assert!(serde_json::from_str::<Nested<u32, u32>>(r#"{"value":1}"#).is_err());
+        assert!(serde_json::from_str::<Nested<u32, u32>>(r#"{"id":1,"id":2}"#).is_err());
+        assert!(serde_json::from_str::<Nested<u32, u32>>(r#"{"id":1,"children":[2]}"#).is_err());
+    }
+
+    #[test]
+    fn test_nested_wide_tree() {
+        let mut tree = Tree::new(None);
+        tree.add_node(Node::new(0, None), None).unwrap();
+        for node_id in 1..=100_000 {
+            tree.add_node(Node::new(node_id, Some(node_id)), Some(&0))
+                .unwrap();
+        }
+        let json = serde_json::to_string(&Nested(tree.clone())).unwrap();
+        let Nested(deserialized) = serde_json::from_str::<Nested<u32, u32>>(&json).unwrap();
+        assert_eq!(deserialized, tree);
+    }
+
+    #[test]
+    fn test_nested_deep_tree() {
+        let deep = |depth: u32| {
+            let mut tree = Tree::new(None);
+            tree.add_node(Node::new(1, None), None).unwrap();
+            for node_id in 2..=depth {
+                tree.add_node(Node::new(node_id, None), Some(&(node_id - 1)))
+                    .unwrap();
+            }
+            tree
+        };
+        // The JSON parser gives up on its own at 128 nested maps and lists, that is 64 levels of nodes.
+        let tree = deep(64);
+        let serialized = serde_json::to_string(&Nested(tree.clone())).unwrap();
+        let Nested(deserialized) = serde_json::from_str::<Nested<u32, u32>>(&serialized).unwrap();
+        assert_eq!(deserialized, tree);
+        let serialized = serde_json::to_string(&Nested(deep(65))).unwrap();
+        assert!(serde_json::from_str::<Nested<u32, u32>>(&serialized).is_err());
+
+        // Values are not parsed, so only the limit of this module applies to them.
+        let tree = deep(MAX_DEPTH as u32);
+        let value = serde_json::to_value(Nested(tree.clone())).unwrap();
+        let Nested(deserialized) = serde_json::from_value::<Nested<u32, u32>>(value).unwrap();
+        assert_eq!(deserialized, tree);
+
+        assert!(serde_json::to_string(&Nested(deep(MAX_DEPTH as u32 + 1))).is_err());
+        let mut value = serde_json::json!({ "id": 0 });
+        for node_id in 1..=MAX_DEPTH {
+            value = serde_json::json!({ "id": node_id, "children": [value] });
+        }
+        assert!(serde_json::from_value::<Nested<u32, u32>>(value).is_err());
+    }
+}
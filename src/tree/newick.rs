@@ -0,0 +1,599 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error::{DuplicateNodeId, InvalidOperation, ParseError};
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::{Node, NodeHandle, Result, Tree};
+use crate::tree::IdSet;
+
+/// The value of a node of a tree read from or written to the Newick format.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::NewickValue;
+///
+/// let value = NewickValue {
+///     label: Some(String::from("Homo sapiens")),
+///     branch_length: Some(String::from("0.0067")),
+///     comments: vec![],
+/// };
+/// assert_eq!(value.get_branch_length(), Some(0.0067));
+/// assert_eq!(value.to_string(), "'Homo sapiens':0.0067");
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NewickValue {
+    /// The label of the node, if the node is named.
+    pub label: Option<String>,
+    /// The length of the branch to the parent of the node, as written in the Newick text.
+    pub branch_length: Option<String>,
+    /// The comments following the node, without their square brackets.
+    pub comments: Vec<String>,
+}
+
+impl NewickValue {
+    /// Get the length of the branch to the parent of the node.
+    ///
+    /// # Returns
+    ///
+    /// The branch length, or `None` if the node has no branch length.
+    pub fn get_branch_length(&self) -> Option<f64> {
+        self.branch_length
+            .as_ref()
+            .and_then(|length| length.parse().ok())
+    }
+
+    /// Check that the comments of the node can be written in the Newick format.
+    ///
+    /// A comment ends at the first `]`, so a comment containing one cannot be written.
+    fn check_comments(&self) -> Result<()> {
+        match self.comments.iter().find(|comment| comment.contains(']')) {
+            Some(comment) => Err(InvalidOperation(format!(
+                "The comment \"{}\" cannot be written in the Newick format because it contains ']'.",
+                comment
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for NewickValue {
+    /// Write the label, branch length and comments of the node as they appear in the Newick format.
+    ///
+    /// Labels containing an underscore are quoted, since other Newick readers turn the underscores of
+    /// unquoted labels into spaces. Writing a comment containing `]` fails, since the comment would
+    /// end early.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.check_comments().is_err() {
+            return Err(FmtError);
+        }
+        if let Some(label) = &self.label {
+            let needs_quotes = label.is_empty()
+                || label
+                    .chars()
+                    .any(|c| c.is_whitespace() || "()[]':;,_".contains(c));
+            if needs_quotes {
+                write!(f, "'{}'", label.replace('\'', "''"))?;
+            } else {
+                write!(f, "{}", label)?;
+            }
+        }
+        if let Some(branch_length) = &self.branch_length {
+            write!(f, ":{}", branch_length)?;
+        }
+        for comment in self.comments.iter() {
+            write!(f, "[{}]", comment)?;
+        }
+        Ok(())
+    }
+}
+
+/// A node read from a Newick text, before it gets an id.
+struct NewickNode {
+    value: NewickValue,
+    children: Vec<usize>,
+}
+
+/// A parser of a single tree in the Newick format.
+///
+/// The parser keeps the subtrees that are still open on an explicit stack, so deeply nested trees do
+/// not overflow the call stack.
+struct NewickParser<'a> {
+    chars: core::iter::Peekable<core::str::CharIndices<'a>>,
+    /// The comments read since the last node was read.
+    comments: Vec<String>,
+    nodes: Vec<NewickNode>,
+}
+
+impl NewickParser<'_> {
+    fn error(&mut self, expected: &str) -> crate::error::Error {
+        match self.chars.peek() {
+            Some((position, c)) => ParseError(format!(
+                "Expected {} but found '{}' at position {}.",
+                expected, c, position
+            )),
+            None => ParseError(format!("Expected {} but the input ended.", expected)),
+        }
+    }
+
+    /// Skip whitespace and read comments.
+    fn skip(&mut self) -> Result<()> {
+        while let Some((_, c)) = self.chars.peek() {
+            match c {
+                c if c.is_whitespace() => {
+                    self.chars.next();
+                }
+                '[' => {
+                    self.chars.next();
+                    let mut comment = String::new();
+                    loop {
+                        match self.chars.next() {
+                            Some((_, ']')) => break,
+                            Some((_, c)) => comment.push(c),
+                            None => {
+                                return Err(ParseError(String::from("Unterminated comment.")));
+                            }
+                        }
+                    }
+                    self.comments.push(comment);
+                }
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Read an unquoted label or branch length.
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some((_, c)) = self
+            .chars
+            .peek()
+            .filter(|(_, c)| !c.is_whitespace() && !"()[]':;,".contains(*c))
+        {
+            word.push(*c);
+            self.chars.next();
+        }
+        word
+    }
+
+    /// Read the label, branch length and comments of a node and store the node.
+    fn node(&mut self, children: Vec<usize>) -> Result<usize> {
+        self.skip()?;
+        let label = if let Some((_, '\'')) = self.chars.peek() {
+            self.chars.next();
+            let mut label = String::new();
+            loop {
+                match self.chars.next() {
+                    Some((_, '\'')) if matches!(self.chars.peek(), Some((_, '\''))) => {
+                        self.chars.next();
+                        label.push('\'');
+                    }
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => label.push(c),
+                    None => return Err(ParseError(String::from("Unterminated quoted label."))),
+                }
+            }
+            Some(label)
+        } else {
+            Some(self.word()).filter(|label| !label.is_empty())
+        };
+        self.skip()?;
+        let mut branch_length = None;
+        if let Some((_, ':')) = self.chars.peek() {
+            self.chars.next();
+            self.skip()?;
+            let length = self.word();
+            if length.parse::<f64>().is_err() {
+                return Err(ParseError(format!("Invalid branch length \"{}\".", length)));
+            }
+            branch_length = Some(length);
+            self.skip()?;
+        }
+        self.nodes.push(NewickNode {
+            value: NewickValue {
+                label,
+                branch_length,
+                comments: core::mem::take(&mut self.comments),
+            },
+            children,
+        });
+        Ok(self.nodes.len() - 1)
+    }
+
+    /// Read the tree and return the index of its root node.
+    fn parse(&mut self) -> Result<usize> {
+        // The children read so far and the comments read before the opening parenthesis of every
+        // subtree that is still open.
+        let mut open: Vec<(Vec<usize>, Vec<String>)> = vec![];
+        loop {
+            self.skip()?;
+            if let Some((_, '(')) = self.chars.peek() {
+                self.chars.next();
+                open.push((vec![], core::mem::take(&mut self.comments)));
+                continue;
+            }
+            let mut node = self.node(vec![])?;
+            loop {
+                match self.chars.peek() {
+                    Some((_, ',')) if !open.is_empty() => {
+                        self.chars.next();
+                        if let Some((siblings, _)) = open.last_mut() {
+                            siblings.push(node);
+                        }
+                        break;
+                    }
+                    Some((_, ')')) if !open.is_empty() => {
+                        self.chars.next();
+                        let (mut children, mut comments) = open.pop().unwrap_or_default();
+                        children.push(node);
+                        comments.append(&mut self.comments);
+                        self.comments = comments;
+                        node = self.node(children)?;
+                    }
+                    Some((_, ';')) if open.is_empty() => {
+                        self.chars.next();
+                        self.skip()?;
+                        if self.chars.peek().is_some() {
+                            return Err(self.error("the end of the input"));
+                        }
+                        return Ok(node);
+                    }
+                    _ if open.is_empty() => return Err(self.error("';'")),
+                    _ => return Err(self.error("',' or ')'")),
+                }
+            }
+        }
+    }
+}
+
+impl<Q> Tree<Q, NewickValue>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+{
+    /// Read a tree from the Newick format.
+    ///
+    /// This method reads a single tree ending with `;` from a Newick text. Every node gets a
+    /// `NewickValue` holding its label, branch length and comments. A named node gets its label parsed
+    /// with `FromStr` as its id. Anonymous nodes, nodes whose label cannot be parsed as an id and nodes
+    /// whose label was already taken by an earlier node, such as repeated bootstrap values of internal
+    /// nodes, get an id from the given id factory instead. The nodes are visited in pre-order, and the
+    /// label is kept in the value of the node either way. Underscores in unquoted labels are kept as
+    /// they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `newick` - The Newick text.
+    /// * `id_factory` - The function generating the ids of the nodes that do not get their label as id.
+    ///
+    /// # Returns
+    ///
+    /// The tree. An error is returned if:
+    /// - The text is not a valid Newick tree.
+    /// - The id factory generates an id that is already used by another node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{NewickValue, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut next_id = 0;
+    /// let tree: Tree<String, NewickValue> = Tree::from_newick("(A:0.1,B:0.2,(C:0.3,D:0.4):0.5);", || {
+    ///     next_id += 1;
+    ///     format!("#{}", next_id)
+    /// })?;
+    /// let root = tree.get_root_node().unwrap();
+    /// assert_eq!(root.get_node_id(), "#1");
+    /// assert_eq!(root.get_children_ids(), vec!["A", "B", "#2"]);
+    /// let c = tree.get_node_by_id(&String::from("C")).unwrap();
+    /// assert_eq!(c.get_value().unwrap().get_branch_length(), Some(0.3));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_newick<F>(newick: &str, mut id_factory: F) -> Result<Self>
+    where
+        Q: FromStr,
+        F: FnMut() -> Q,
+    {
+        let mut parser = NewickParser {
+            chars: newick.char_indices().peekable(),
+            comments: vec![],
+            nodes: vec![],
+        };
+        let root = parser.parse()?;
+        let mut newick_nodes = parser.nodes;
+
+        // Give the nodes their ids in pre-order, so that generated ids follow the order of the text.
+        let mut ids = vec![];
+        let mut positions = vec![0; newick_nodes.len()];
+        let mut parents = vec![None; newick_nodes.len()];
+        let mut order = vec![];
+        let mut node_ids = IdSet::new();
+        let mut stack = vec![root];
+        while let Some(index) = stack.pop() {
+            let node_id = newick_nodes[index]
+                .value
+                .label
+                .as_ref()
+                .and_then(|label| label.parse::<Q>().ok())
+                .filter(|node_id| !node_ids.contains(node_id))
+                .unwrap_or_else(&mut id_factory);
+            if !node_ids.insert(node_id.clone()) {
+                return Err(DuplicateNodeId(node_id.to_string()));
+            }
+            positions[index] = ids.len();
+            ids.push(node_id);
+            order.push(index);
+            for child in newick_nodes[index].children.iter().rev() {
+                parents[*child] = Some(index);
+                stack.push(*child);
+            }
+        }
+        let id = |index: usize| ids[positions[index]].clone();
+        let nodes = order
+            .into_iter()
+            .map(|index| {
+                let newick_node = &mut newick_nodes[index];
+                let node = Node::new(id(index), Some(core::mem::take(&mut newick_node.value)));
+                node.set_parent_id(parents[index].map(id));
+                node.set_children_ids(
+                    newick_node
+                        .children
+                        .iter()
+                        .map(|child| id(*child))
+                        .collect(),
+                );
+                node
+            })
+            .collect();
+        Ok(Tree::from_parts(None, Nodes::new(nodes)))
+    }
+
+    /// Write the tree in the Newick format.
+    ///
+    /// This method writes the tree as a Newick text ending with `;`. The labels, branch lengths and
+    /// comments of the nodes are taken from their values, and labels are quoted when needed. The node
+    /// ids are not written, so nodes without a label are anonymous.
+    ///
+    /// # Returns
+    ///
+    /// The Newick text. An error is returned if the tree is empty or has more than one root node, or
+    /// if a comment contains `]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{NewickValue, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let named = |label: &str| NewickValue {
+    ///     label: Some(String::from(label)),
+    ///     ..NewickValue::default()
+    /// };
+    /// let mut tree: Tree<i32, NewickValue> = Tree::new(Some("Sample Tree"));
+    /// let root = tree.add_node(Node::new(1, Some(named("Hominidae"))), None)?;
+    /// tree.add_node(Node::new(2, Some(named("Homo sapiens"))), Some(&root))?;
+    /// tree.add_node(Node::new(3, Some(named("Pan"))), Some(&root))?;
+    /// assert_eq!(tree.to_newick()?, "('Homo sapiens',Pan)Hominidae;");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_newick(&self) -> Result<String> {
+        enum Step {
+            Open(NodeHandle),
+            Separate,
+            Close(NodeHandle),
+        }
+
+        let roots = self.root_handles().collect::<Vec<_>>();
+        let root =
+            match roots.as_slice() {
+                [root] => *root,
+                [] => {
+                    return Err(InvalidOperation(String::from(
+                        "An empty tree cannot be written in the Newick format.",
+                    )))
+                }
+                _ => return Err(InvalidOperation(String::from(
+                    "A tree with more than one root node cannot be written in the Newick format.",
+                ))),
+            };
+        let mut newick = String::new();
+        let mut stack = vec![Step::Open(root)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Open(handle) => {
+                    // The handles come from the arena, so they always resolve.
+                    let (Some(node), Some(children)) =
+                        (self.arena.get(handle), self.arena.children(handle))
+                    else {
+                        continue;
+                    };
+                    let value = node.get_value().unwrap_or_default();
+                    value.check_comments()?;
+                    if children.len() == 0 {
+                        write!(newick, "{}", value)?;
+                        continue;
+                    }
+                    newick.push('(');
+                    stack.push(Step::Close(handle));
+                    for (position, child) in children.rev().enumerate() {
+                        if position > 0 {
+                            stack.push(Step::Separate);
+                        }
+                        stack.push(Step::Open(child));
+                    }
+                }
+                Step::Separate => newick.push(','),
+                Step::Close(handle) => {
+                    let Some(node) = self.arena.get(handle) else {
+                        continue;
+                    };
+                    write!(newick, "){}", node.get_value().unwrap_or_default())?;
+                }
+            }
+        }
+        newick.push(';');
+        Ok(newick)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(newick: &str) -> Result<Tree<String, NewickValue>> {
+        let mut next_id = 0;
+        Tree::from_newick(newick, || {
+            next_id += 1;
+            format!("#{}", next_id)
+        })
+    }
+
+    #[test]
+    fn test_from_newick() -> Result<()> {
+        let tree = read("((A,'B c':2)95:1[&&NHX:S=x], [leading]'it''s' : 1e-3 ,)root;")?;
+        tree.debug_assert_valid();
+        let root = tree.get_root_node().unwrap();
+        assert_eq!(root.get_node_id(), "root");
+        assert_eq!(root.get_children_ids(), vec!["95", "it's", "#1"]);
+        let internal = tree
+            .get_node_by_id(&String::from("95"))
+            .unwrap()
+            .get_value()
+            .unwrap();
+        assert_eq!(internal.get_branch_length(), Some(1.0));
+        assert_eq!(internal.comments, vec!["&&NHX:S=x"]);
+        let quoted = tree
+            .get_node_by_id(&String::from("it's"))
+            .unwrap()
+            .get_value()
+            .unwrap();
+        assert_eq!(quoted.branch_length.as_deref(), Some("1e-3"));
+        assert_eq!(quoted.comments, vec!["leading"]);
+        assert_eq!(
+            tree.get_node_by_id(&String::from("B c"))
+                .unwrap()
+                .get_parent_id(),
+            Some(String::from("95"))
+        );
+        assert_eq!(
+            tree.get_node_by_id(&String::from("#1"))
+                .unwrap()
+                .get_value(),
+            Some(NewickValue::default())
+        );
+
+        let single = read("A;")?;
+        assert_eq!(single.get_nodes().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_newick_errors() {
+        assert!(matches!(read("(A,B)"), Err(ParseError(_))));
+        assert!(matches!(read("(A,B;"), Err(ParseError(_))));
+        assert!(matches!(read("(A,B));"), Err(ParseError(_))));
+        assert!(matches!(read("A,B;"), Err(ParseError(_))));
+        assert!(matches!(read("(A:x,B);"), Err(ParseError(_))));
+        assert!(matches!(read("('A,B);"), Err(ParseError(_))));
+        assert!(matches!(read("(A[,B);"), Err(ParseError(_))));
+        assert!(matches!(read("(A,B); C;"), Err(ParseError(_))));
+        let repeated: Result<Tree<i32, NewickValue>> = Tree::from_newick("(1,B);", || 1);
+        assert!(matches!(
+            repeated,
+            Err(DuplicateNodeId(node_id)) if node_id == "1"
+        ));
+    }
+
+    #[test]
+    fn test_from_newick_labels_that_are_not_ids() -> Result<()> {
+        let tree = read("(A,A);")?;
+        let root = tree.get_root_node().unwrap();
+        assert_eq!(root.get_children_ids(), vec!["A", "#2"]);
+
+        let mut next_id = 100;
+        let numbers: Tree<i32, NewickValue> = Tree::from_newick("(1,B);", || {
+            next_id += 1;
+            next_id
+        })?;
+        let b = numbers.get_node_by_id(&102).unwrap().get_value().unwrap();
+        assert_eq!(b.label.as_deref(), Some("B"));
+        assert_eq!(
+            numbers.get_node_by_id(&101).unwrap().get_children_ids(),
+            vec![1, 102]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_newick_repeated_bootstrap_values() -> Result<()> {
+        let text = "((A:0.1,B:0.2)100:0.05,(C:0.3,D:0.4)100:0.05,(E:0.5,F:0.6)95:0.1)root;";
+        let tree = read(text)?;
+        tree.debug_assert_valid();
+        assert_eq!(tree.get_nodes().len(), 10);
+        assert_eq!(
+            tree.get_root_node().unwrap().get_children_ids(),
+            vec!["100", "#1", "95"]
+        );
+        let repeated = tree.get_node_by_id(&String::from("#1")).unwrap();
+        assert_eq!(repeated.get_value().unwrap().label.as_deref(), Some("100"));
+        assert_eq!(repeated.get_children_ids(), vec!["C", "D"]);
+        assert_eq!(tree.to_newick()?, text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_newick() -> Result<()> {
+        let text = "((A,'B c':2)95:1[&&NHX:S=x],'it''s':1e-3[leading],)root;";
+        let tree = read(text)?;
+        assert_eq!(tree.to_newick()?, text);
+
+        let mut forest: Tree<i32, NewickValue> = Tree::new_forest(None);
+        assert!(forest.to_newick().is_err());
+        forest.add_node(Node::new(1, None), None)?;
+        assert_eq!(forest.to_newick()?, ";");
+        forest.add_node(Node::new(2, None), None)?;
+        assert!(forest.to_newick().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_newick_escaping() -> Result<()> {
+        let text = "('Homo_sapiens',Pan)'Homo_Pan';";
+        let tree = read(text)?;
+        assert_eq!(tree.to_newick()?, text);
+        assert_eq!(
+            tree.get_node_by_id(&String::from("Homo_sapiens"))
+                .and_then(|node| node.get_value())
+                .and_then(|value| value.label),
+            Some(String::from("Homo_sapiens"))
+        );
+
+        let value = NewickValue {
+            label: Some(String::from("A")),
+            branch_length: None,
+            comments: vec![String::from("a]b")],
+        };
+        let mut written = String::new();
+        assert!(write!(written, "{}", value).is_err());
+        let mut tree: Tree<i32, NewickValue> = Tree::new(None);
+        tree.add_node(Node::new(1, None), None)?;
+        tree.add_node(Node::new(2, Some(value)), Some(&1))?;
+        assert!(matches!(tree.to_newick(), Err(InvalidOperation(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_newick_deep_tree() -> Result<()> {
+        let depth = 100_000;
+        let text = format!("{}A{};", "(".repeat(depth), ")".repeat(depth));
+        let tree = read(&text)?;
+        assert_eq!(tree.get_nodes().len(), depth + 1);
+        assert_eq!(tree.to_newick()?, text);
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::{Node, Result, Tree};
+use crate::tree::iter::PreOrderIter;
+
+/// The decision taken by the visitor of [`Tree::search`] for a node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SearchAction {
+    /// The node matches and the search continues with its children.
+    Match,
+    /// The node does not match and the search continues with its children.
+    Continue,
+    /// The node does not match and none of its descendants can match, so they are skipped.
+    Prune,
+    /// The node does not match and the search stops.
+    Stop,
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Find the first node that matches a predicate.
+    ///
+    /// This method walks the tree in pre-order, one tree after the other in a forest, and stops at the
+    /// first node for which the predicate returns `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The function used to check if a node matches.
+    ///
+    /// # Returns
+    ///
+    /// The first node that matches the predicate, or `None` if no node matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(3)), Some(&node_1))?;
+    ///
+    /// let node = tree.find(|node| node.get_value() == Some(3));
+    /// assert_eq!(node.map(|node| node.get_node_id()), Some(node_2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find<P>(&self, mut predicate: P) -> Option<Node<Q, T>>
+    where
+        P: FnMut(&Node<Q, T>) -> bool,
+    {
+        self.root_handles()
+            .flat_map(|root| PreOrderIter::new(self, root).handles())
+            .filter_map(|(_, handle)| self.get_node_by_handle(handle))
+            .find(|node| predicate(node))
+            .cloned()
+    }
+
+    /// Find all the nodes that match a predicate.
+    ///
+    /// This method walks the whole tree in pre-order, one tree after the other in a forest, and
+    /// collects every node for which the predicate returns `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The function used to check if a node matches.
+    ///
+    /// # Returns
+    ///
+    /// The nodes that match the predicate in pre-order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+    ///
+    /// let nodes = tree.find_all(|node| node.get_value().is_some_and(|value| value > 2));
+    /// assert_eq!(nodes.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_all<P>(&self, mut predicate: P) -> Nodes<Q, T>
+    where
+        P: FnMut(&Node<Q, T>) -> bool,
+    {
+        let nodes = self
+            .root_handles()
+            .flat_map(|root| PreOrderIter::new(self, root).handles())
+            .filter_map(|(_, handle)| self.get_node_by_handle(handle))
+            .filter(|node| predicate(node))
+            .cloned()
+            .collect();
+        Nodes::new(nodes)
+    }
+
+    /// Find the first node of a subtree that matches a predicate.
+    ///
+    /// This method walks the subtree rooted at the given node in pre-order and stops at the first node
+    /// for which the predicate returns `true`. The node itself is checked first.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root of the subtree to search.
+    /// * `predicate` - The function used to check if a node matches.
+    ///
+    /// # Returns
+    ///
+    /// The first node of the subtree that matches the predicate, or `None` if no node matches. This
+    /// method returns an error if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(5)), Some(&node_1))?;
+    /// let node_4 = tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    ///
+    /// let node = tree.find_in_subtree(&node_2, |node| node.get_value() == Some(5))?;
+    /// assert_eq!(node.map(|node| node.get_node_id()), Some(node_4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn find_in_subtree<P>(&self, node_id: &Q, mut predicate: P) -> Result<Option<Node<Q, T>>>
+    where
+        P: FnMut(&Node<Q, T>) -> bool,
+    {
+        Ok(self.iter_pre_order(node_id)?.find(|node| predicate(node)))
+    }
+
+    /// Get the id of the first node that holds a value.
+    ///
+    /// This method walks the tree in pre-order, one tree after the other in a forest, and stops at the
+    /// first node whose value is equal to the given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to look for.
+    ///
+    /// # Returns
+    ///
+    /// The id of the first node that holds the value, or `None` if no node holds it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some("Harry")), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some("Jane")), Some(&node_1))?;
+    /// assert_eq!(tree.position_of_value(&"Jane"), Some(node_2));
+    /// assert_eq!(tree.position_of_value(&"John"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn position_of_value(&self, value: &T) -> Option<Q> {
+        self.find(|node| node.with_value(|node_value| node_value == Some(value)))
+            .map(|node| node.get_node_id())
+    }
+
+    /// Search a subtree, letting a visitor decide which branches to explore.
+    ///
+    /// This method walks the subtree rooted at the given node in pre-order and asks the visitor what to
+    /// do with every node it reaches. The visitor can mark the node as a match, skip all the
+    /// descendants of the node when none of them can match, or stop the search altogether.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root of the subtree to search.
+    /// * `visitor` - The function that decides what to do with every node.
+    ///
+    /// # Returns
+    ///
+    /// The nodes marked as a match in pre-order. This method returns an error if the node is not found
+    /// in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, SearchAction, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<&str, u32> = Tree::new(Some("Sizes"));
+    /// let root = tree.add_node(Node::new("/", Some(30)), None)?;
+    /// let docs = tree.add_node(Node::new("/docs", Some(25)), Some(&root))?;
+    /// tree.add_node(Node::new("/docs/big", Some(20)), Some(&docs))?;
+    /// let tmp = tree.add_node(Node::new("/tmp", Some(5)), Some(&root))?;
+    /// tree.add_node(Node::new("/tmp/small", Some(5)), Some(&tmp))?;
+    ///
+    /// // Find the directories bigger than 10, skipping the directories that are too small to
+    /// // contain one.
+    /// let nodes = tree.search(&root, |node| match node.get_value() {
+    ///     Some(size) if size > 10 => SearchAction::Match,
+    ///     _ => SearchAction::Prune,
+    /// })?;
+    /// let ids = nodes.iter().map(|node| node.get_node_id()).collect::<Vec<_>>();
+    /// assert_eq!(ids, vec!["/", "/docs", "/docs/big"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search<F>(&self, node_id: &Q, mut visitor: F) -> Result<Nodes<Q, T>>
+    where
+        F: FnMut(&Node<Q, T>) -> SearchAction,
+    {
+        let mut matches = vec![];
+        let mut stack = vec![self.find_handle(node_id)?];
+        while let Some(handle) = stack.pop() {
+            let Some(node) = self.get_node_by_handle(handle) else {
+                continue;
+            };
+            match visitor(node) {
+                SearchAction::Prune => continue,
+                SearchAction::Stop => break,
+                SearchAction::Continue => {}
+                SearchAction::Match => matches.push(node.clone()),
+            }
+            stack.extend(self.arena.children(handle).into_iter().flatten().rev());
+        }
+        Ok(Nodes::new(matches))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::tests::sample_tree;
+
+    fn ids(nodes: Nodes<i32, i32>) -> Vec<i32> {
+        nodes.iter().map(|node| node.get_node_id()).collect()
+    }
+
+    #[test]
+    fn test_find() -> Result<()> {
+        let tree = sample_tree()?;
+        let mut visited = 0;
+        let node = tree.find(|node| {
+            visited += 1;
+            node.get_value() == Some(6)
+        });
+        assert_eq!(node.map(|node| node.get_node_id()), Some(5));
+        // The search stops at the first match.
+        assert_eq!(visited, 4);
+        assert!(tree.find(|node| node.get_value() == Some(9)).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_all() -> Result<()> {
+        let tree = sample_tree()?;
+        let nodes = tree.find_all(|node| node.get_value() == Some(6));
+        assert_eq!(ids(nodes), vec![5, 3]);
+        assert!(tree.find_all(|_| false).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_in_subtree() -> Result<()> {
+        let tree = sample_tree()?;
+        let node = tree.find_in_subtree(&3, |node| node.get_value() == Some(6))?;
+        assert_eq!(node.map(|node| node.get_node_id()), Some(3));
+        let node = tree.find_in_subtree(&2, |node| node.get_value() == Some(7))?;
+        assert!(node.is_none());
+        assert!(tree.find_in_subtree(&8, |_| true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_of_value() -> Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), None)?;
+        forest.add_node(Node::new(3, Some(4)), Some(&2))?;
+        assert_eq!(forest.position_of_value(&4), Some(3));
+        assert_eq!(forest.position_of_value(&5), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search() -> Result<()> {
+        let tree = sample_tree()?;
+        let mut visited = vec![];
+        let nodes = tree.search(&1, |node| {
+            visited.push(node.get_node_id());
+            match node.get_node_id() {
+                2 => SearchAction::Prune,
+                6 => SearchAction::Match,
+                _ => SearchAction::Continue,
+            }
+        })?;
+        assert_eq!(ids(nodes), vec![6]);
+        assert_eq!(visited, vec![1, 2, 3, 6]);
+
+        let nodes = tree.search(&1, |node| match node.get_node_id() {
+            5 => SearchAction::Stop,
+            _ => SearchAction::Match,
+        })?;
+        assert_eq!(ids(nodes), vec![1, 2, 4]);
+        assert!(tree.search(&8, |_| SearchAction::Match).is_err());
+        Ok(())
+    }
+}
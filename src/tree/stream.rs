@@ -0,0 +1,365 @@
+use futures_core::Stream;
+
+use crate::error::Error::InvalidOperation;
+use crate::lib::*;
+use crate::prelude::{Node, Result, TraversalStrategy, Tree};
+
+/// A stream over the nodes of a tree in traversal order.
+///
+/// The stream is created by `Tree::stream`. The traversal order is computed when the stream is created,
+/// and the nodes are looked up in the tree as the stream is polled. The stream is always ready and does
+/// not depend on a particular runtime.
+pub struct TraversalStream<'a, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    node_ids: vec::IntoIter<Q>,
+}
+
+// The stream never pins any of its fields.
+impl<Q, T> Unpin for TraversalStream<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+}
+
+impl<Q, T> Stream for TraversalStream<'_, Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    type Item = Node<Q, T>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let tree = this.tree;
+        Poll::Ready(
+            this.node_ids
+                .by_ref()
+                .find_map(|node_id| tree.get_node_by_id(&node_id)),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.node_ids.size_hint()
+    }
+}
+
+/// The future driving the visitor futures of `Tree::visit_async`.
+struct Visit<'a, Q, T, F, Fut>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    tree: &'a Tree<Q, T>,
+    node_ids: vec::IntoIter<Q>,
+    visitor: F,
+    pending: Vec<Pin<Box<Fut>>>,
+    limit: usize,
+}
+
+// The visitor futures are pinned on the heap, so the future itself never needs to be pinned.
+impl<Q, T, F, Fut> Unpin for Visit<'_, Q, T, F, Fut>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+}
+
+impl<Q, T, F, Fut> Future for Visit<'_, Q, T, F, Fut>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+    F: FnMut(Node<Q, T>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        loop {
+            while this.pending.len() < this.limit {
+                let Some(node_id) = this.node_ids.next() else {
+                    break;
+                };
+                if let Some(node) = this.tree.get_node_by_id(&node_id) {
+                    this.pending.push(Box::pin((this.visitor)(node)));
+                }
+            }
+            let pending = this.pending.len();
+            this.pending
+                .retain_mut(|future| future.as_mut().poll(cx).is_pending());
+            if this.pending.is_empty() && this.node_ids.len() == 0 {
+                return Poll::Ready(());
+            }
+            // Start the next visitors only when some of the running ones completed.
+            if this.pending.len() == pending {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Stream the nodes of a subtree in traversal order.
+    ///
+    /// This method returns a `futures_core::Stream` yielding the nodes of the subtree rooted at the
+    /// given node in the given traversal order. The stream can be consumed by any executor.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order in which the nodes are yielded.
+    /// * `node_id` - The id of the root of the subtree to stream.
+    ///
+    /// # Returns
+    ///
+    /// The stream over the nodes. An error is returned if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use core::future::poll_fn;
+    /// # use core::pin::Pin;
+    /// # use futures_core::Stream;
+    /// # use tree_ds::prelude::{Node, TraversalStrategy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+    ///
+    /// let mut stream = tree.stream(TraversalStrategy::PostOrder, &node_1)?;
+    /// let mut node_ids = vec![];
+    /// pollster::block_on(async {
+    ///     while let Some(node) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+    ///         node_ids.push(node.get_node_id());
+    ///     }
+    /// });
+    /// assert_eq!(node_ids, vec![node_2, node_3, node_1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(
+        &self,
+        order: TraversalStrategy,
+        node_id: &Q,
+    ) -> Result<TraversalStream<'_, Q, T>> {
+        Ok(TraversalStream {
+            tree: self,
+            node_ids: self.traverse(order, node_id)?.into_iter(),
+        })
+    }
+
+    /// Visit the nodes of a subtree with an asynchronous visitor.
+    ///
+    /// This method calls the visitor on every node of the subtree rooted at the given node in the given
+    /// traversal order, and awaits the returned futures. At most `limit` futures are awaited at the
+    /// same time; the next node is only visited once one of them completes. The futures may complete
+    /// in any order. The visitor futures are polled by the returned future, so it does not depend on
+    /// a particular runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The order in which the visitor is called on the nodes.
+    /// * `node_id` - The id of the root of the subtree to visit.
+    /// * `limit` - The maximum number of visitor futures awaited at the same time.
+    /// * `visitor` - The function returning the future to await for a node.
+    ///
+    /// # Returns
+    ///
+    /// An error is returned if the node is not found in the tree or if the limit is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, TraversalStrategy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// pollster::block_on(tree.visit_async(TraversalStrategy::PreOrder, &node_1, 4, |node| async move {
+    ///     // Fetch the new value from somewhere else.
+    ///     node.update_value(|value| *value *= 10);
+    /// }))?;
+    /// assert_eq!(tree.get_node_by_id(&node_2).unwrap().get_value(), Some(30));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn visit_async<F, Fut>(
+        &self,
+        order: TraversalStrategy,
+        node_id: &Q,
+        limit: usize,
+        visitor: F,
+    ) -> Result<()>
+    where
+        F: FnMut(Node<Q, T>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        if limit == 0 {
+            return Err(InvalidOperation(String::from(
+                "The concurrency limit must be at least one.",
+            )));
+        }
+        Visit {
+            tree: self,
+            node_ids: self.traverse(order, node_id)?.into_iter(),
+            visitor,
+            pending: vec![],
+            limit,
+        }
+        .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::{Cell, RefCell};
+    use core::future::poll_fn;
+
+    use crate::tree::tests::sample_tree;
+
+    /// A future returning `Poll::Pending` the given number of times before completing.
+    fn yield_times(mut times: usize) -> impl Future<Output = ()> {
+        poll_fn(move |cx| {
+            if times == 0 {
+                return Poll::Ready(());
+            }
+            times -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+    }
+
+    fn collect<Q, T>(mut stream: TraversalStream<'_, Q, T>) -> Vec<Q>
+    where
+        Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+        T: PartialEq + Eq + Clone,
+    {
+        pollster::block_on(async {
+            let mut node_ids = vec![];
+            while let Some(node) = poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await {
+                node_ids.push(node.get_node_id());
+            }
+            node_ids
+        })
+    }
+
+    #[test]
+    fn test_stream() -> Result<()> {
+        let tree = sample_tree()?;
+        for order in [
+            TraversalStrategy::PreOrder,
+            TraversalStrategy::PostOrder,
+            TraversalStrategy::InOrder,
+            TraversalStrategy::LevelOrder,
+        ] {
+            assert_eq!(collect(tree.stream(order, &1)?), tree.traverse(order, &1)?);
+        }
+        assert_eq!(
+            tree.stream(TraversalStrategy::PreOrder, &2)?.size_hint(),
+            (3, Some(3))
+        );
+        assert!(tree.stream(TraversalStrategy::PreOrder, &7).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_async() -> Result<()> {
+        let tree = sample_tree()?;
+        let visited = RefCell::new(vec![]);
+        pollster::block_on(
+            tree.visit_async(TraversalStrategy::PreOrder, &1, 1, |node| {
+                let visited = &visited;
+                async move {
+                    yield_times(2).await;
+                    visited.borrow_mut().push(node.get_node_id());
+                }
+            }),
+        )?;
+        // Visiting one node at a time keeps the traversal order.
+        assert_eq!(
+            visited.take(),
+            tree.traverse(TraversalStrategy::PreOrder, &1)?
+        );
+
+        pollster::block_on(tree.visit_async(
+            TraversalStrategy::PreOrder,
+            &2,
+            2,
+            |node| async move {
+                node.update_value(|value| *value *= 10);
+            },
+        ))?;
+        assert_eq!(tree.get_node_by_id(&4).unwrap().get_value(), Some(50));
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value(), Some(6));
+
+        assert!(pollster::block_on(tree.visit_async(
+            TraversalStrategy::PreOrder,
+            &1,
+            0,
+            |_| async {}
+        ))
+        .is_err());
+        assert!(pollster::block_on(tree.visit_async(
+            TraversalStrategy::PreOrder,
+            &7,
+            1,
+            |_| async {}
+        ))
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_async_is_send() -> Result<()> {
+        fn assert_send<F: Future + Send>(future: F) -> F {
+            future
+        }
+
+        // The future can be spawned on multi-threaded runtimes.
+        let tree = sample_tree()?;
+        let future =
+            assert_send(
+                tree.visit_async(TraversalStrategy::PreOrder, &1, 2, |node| async move {
+                    node.update_value(|value| *value += 1);
+                }),
+            );
+        pollster::block_on(future)?;
+        assert_eq!(tree.get_node_by_id(&6).unwrap().get_value(), Some(8));
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_async_limit() -> Result<()> {
+        let tree = sample_tree()?;
+        let running = Cell::new(0);
+        let most_running = Cell::new(0);
+        pollster::block_on(
+            tree.visit_async(TraversalStrategy::LevelOrder, &1, 4, |node| {
+                let (running, most_running) = (&running, &most_running);
+                async move {
+                    running.set(running.get() + 1);
+                    most_running.set(most_running.get().max(running.get()));
+                    yield_times(node.get_node_id() as usize).await;
+                    running.set(running.get() - 1);
+                }
+            }),
+        )?;
+        assert_eq!(running.get(), 0);
+        assert_eq!(most_running.get(), 4);
+        Ok(())
+    }
+}
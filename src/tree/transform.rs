@@ -0,0 +1,413 @@
+use crate::error::Error::{DuplicateNodeId, InvalidOperation, NodeNotFound};
+use crate::lib::*;
+use crate::node::Nodes;
+use crate::prelude::{Node, NodeHandle, NodeRemovalStrategy, Result, Tree};
+use crate::tree::iter::PostOrderIter;
+use crate::tree::{IdMap, IdSet};
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create a new tree with the same shape and mapped values.
+    ///
+    /// This method creates a new tree with the same name, node ids and shape as the tree. The value of
+    /// every node of the new tree is computed by the given function from the matching node of the tree.
+    /// The new tree does not share any node with the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function computing the value of a node of the new tree.
+    ///
+    /// # Returns
+    ///
+    /// The new tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let labels: Tree<i32, String> = tree.map_values(|node| node.get_value().map(|v| v.to_string()));
+    /// assert_eq!(labels.get_node_by_id(&node_2).unwrap().get_value(), Some(String::from("3")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map_values<U, F>(&self, mut f: F) -> Tree<Q, U>
+    where
+        U: PartialEq + Eq + Clone,
+        F: FnMut(&Node<Q, T>) -> Option<U>,
+    {
+        let nodes = self
+            .arena
+            .handles()
+            .filter_map(|handle| {
+                let node = self.arena.get(handle)?;
+                let mapped_node = Node::new(node.get_node_id(), f(node));
+                mapped_node.set_parent_id(
+                    self.arena
+                        .parent(handle)
+                        .and_then(|parent| self.id_of(parent)),
+                );
+                mapped_node.set_children_ids(self.children_ids(handle));
+                Some(mapped_node)
+            })
+            .collect();
+        let mut tree = Tree::from_parts(self.name.clone(), Nodes::new(nodes));
+        tree.forest = self.forest;
+        tree
+    }
+
+    /// Create a new tree with the same shape and mapped node ids.
+    ///
+    /// This method creates a new tree with the same name, values and shape as the tree. The id of every
+    /// node of the new tree is computed by the given function from the id of the matching node of the
+    /// tree. The new tree does not share any node with the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function computing the id of a node of the new tree.
+    ///
+    /// # Returns
+    ///
+    /// The new tree. An error is returned if the function maps two nodes to the same id.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    ///
+    /// let renamed: Tree<String, i32> = tree.map_ids(|node_id| format!("node-{}", node_id))?;
+    /// let node = renamed.get_node_by_id(&String::from("node-2")).unwrap();
+    /// assert_eq!(node.get_parent_id(), Some(String::from("node-1")));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn map_ids<R, F>(&self, mut f: F) -> Result<Tree<R, T>>
+    where
+        R: PartialEq + Eq + Clone + Display + Hash + Ord,
+        F: FnMut(&Q) -> R,
+    {
+        let mut ids = IdMap::new();
+        let mut mapped_ids = IdSet::new();
+        for node in self.arena.nodes().iter() {
+            let node_id = node.get_node_id();
+            let mapped_id = f(&node_id);
+            if !mapped_ids.insert(mapped_id.clone()) {
+                return Err(DuplicateNodeId(mapped_id.to_string()));
+            }
+            ids.insert(node_id, mapped_id);
+        }
+        let nodes = self
+            .arena
+            .handles()
+            .filter_map(|handle| {
+                let node = self.arena.get(handle)?;
+                let mapped_node =
+                    Node::new(ids.get(&node.get_node_id())?.clone(), node.get_value());
+                mapped_node.set_parent_id(
+                    self.arena
+                        .parent(handle)
+                        .and_then(|parent| self.id_of(parent))
+                        .and_then(|parent_id| ids.get(&parent_id).cloned()),
+                );
+                mapped_node.set_children_ids(
+                    self.children_ids(handle)
+                        .iter()
+                        .filter_map(|child_id| ids.get(child_id).cloned())
+                        .collect(),
+                );
+                Some(mapped_node)
+            })
+            .collect();
+        let mut tree = Tree::from_parts(self.name.clone(), Nodes::new(nodes));
+        tree.forest = self.forest;
+        Ok(tree)
+    }
+
+    /// Create a new tree with the nodes that match a predicate.
+    ///
+    /// This method creates a new tree with the same name as the tree, holding copies of the nodes for
+    /// which the predicate returns `true`. The strategy decides what happens to the descendants of a
+    /// node that does not match:
+    /// - `NodeRemovalStrategy::RemoveNodeAndChildren` leaves out the whole subtree of the node.
+    /// - `NodeRemovalStrategy::RetainChildren` checks the descendants of the node as well and attaches
+    ///   the ones that match to the closest ancestor that matches, in place of the node.
+    ///
+    /// The new tree does not share any node with the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The function used to check if a node is kept.
+    /// * `strategy` - What to do with the descendants of the nodes that are not kept.
+    ///
+    /// # Returns
+    ///
+    /// The new tree. An error is returned if the tree is not a forest and the new tree would have more
+    /// than one root node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, NodeRemovalStrategy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(4)), Some(&node_2))?;
+    ///
+    /// let is_even = |node: &Node<i32, i32>| node.get_value().is_some_and(|value| value % 2 == 0);
+    /// let spliced = tree.filter(is_even, NodeRemovalStrategy::RetainChildren)?;
+    /// assert_eq!(spliced.get_node_by_id(&node_1).unwrap().get_children_ids(), vec![node_3]);
+    ///
+    /// let pruned = tree.filter(is_even, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+    /// assert_eq!(pruned.get_nodes().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn filter<P>(&self, mut predicate: P, strategy: NodeRemovalStrategy) -> Result<Tree<Q, T>>
+    where
+        P: FnMut(&Node<Q, T>) -> bool,
+    {
+        let mut kept = vec![];
+        let mut children = IdMap::<Q, Vec<Q>>::new();
+        let mut roots = 0;
+        // Walk every tree in pre-order along with the closest kept ancestor of every node so that the
+        // spliced children keep their order.
+        let mut stack = self
+            .root_handles()
+            .map(|root| (root, None))
+            .collect::<Vec<(NodeHandle, Option<Q>)>>();
+        stack.reverse();
+        while let Some((handle, parent_id)) = stack.pop() {
+            let Some(node) = self.arena.get(handle) else {
+                continue;
+            };
+            let node_id = node.get_node_id();
+            let is_kept = predicate(node);
+            if is_kept {
+                let kept_node = Node::new(node_id.clone(), node.get_value());
+                kept_node.set_parent_id(parent_id.clone());
+                match parent_id.as_ref() {
+                    Some(parent_id) => children
+                        .entry(parent_id.clone())
+                        .or_default()
+                        .push(node_id.clone()),
+                    None => roots += 1,
+                }
+                kept.push(kept_node);
+            } else if let NodeRemovalStrategy::RemoveNodeAndChildren = strategy {
+                continue;
+            }
+            let children_parent_id = if is_kept { Some(node_id) } else { parent_id };
+            stack.extend(
+                self.arena
+                    .children(handle)
+                    .into_iter()
+                    .flatten()
+                    .rev()
+                    .map(|child| (child, children_parent_id.clone())),
+            );
+        }
+        if roots > 1 && !self.forest {
+            return Err(InvalidOperation(String::from(
+                "Filtering the tree would leave more than one root node.",
+            )));
+        }
+        for node in kept.iter() {
+            node.set_children_ids(children.remove(&node.get_node_id()).unwrap_or_default());
+        }
+        let mut tree = Tree::from_parts(self.name.clone(), Nodes::new(kept));
+        tree.forest = self.forest;
+        Ok(tree)
+    }
+
+    /// Fold a subtree from the leaves up.
+    ///
+    /// This method computes a result for every node of the subtree rooted at the given node, children
+    /// first. The result of a node is computed by the given function from the node and the results of
+    /// its children, in the order of the children.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The id of the root of the subtree to fold.
+    /// * `f` - The function computing the result of a node from the results of its children.
+    ///
+    /// # Returns
+    ///
+    /// The result of the given node. An error is returned if the node is not found in the tree.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+    /// tree.add_node(Node::new(4, Some(5)), Some(&node_2))?;
+    ///
+    /// // Sum the values of every subtree.
+    /// let total = tree.fold(&node_1, |node, children: Vec<i32>| {
+    ///     node.get_value().unwrap_or_default() + children.iter().sum::<i32>()
+    /// })?;
+    /// assert_eq!(total, 14);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fold<R, F>(&self, node_id: &Q, mut f: F) -> Result<R>
+    where
+        F: FnMut(&Node<Q, T>, Vec<R>) -> R,
+    {
+        let root = self.find_handle(node_id)?;
+        let mut results = IdMap::new();
+        for (_, handle) in PostOrderIter::new(self, root).handles() {
+            let Some(node) = self.arena.get(handle) else {
+                continue;
+            };
+            let children = self
+                .arena
+                .children(handle)
+                .into_iter()
+                .flatten()
+                .filter_map(|child| results.remove(&child))
+                .collect();
+            let result = f(node, children);
+            results.insert(handle, result);
+        }
+        results
+            .remove(&root)
+            .ok_or(NodeNotFound(node_id.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::TraversalStrategy;
+    use crate::tree::tests::sample_tree;
+
+    fn is_even(node: &Node<i32, i32>) -> bool {
+        node.get_value().is_some_and(|value| value % 2 == 0)
+    }
+
+    #[test]
+    fn test_map_values() -> Result<()> {
+        let tree = sample_tree()?;
+        let mapped = tree.map_values(|node| node.get_value().map(|value| value * 10));
+        mapped.debug_assert_valid();
+        assert_eq!(mapped.get_name(), Some("Sample Tree"));
+        assert_eq!(mapped.get_node_by_id(&3).unwrap().get_value(), Some(60));
+        assert_eq!(
+            mapped.traverse(TraversalStrategy::PreOrder, &1)?,
+            tree.traverse(TraversalStrategy::PreOrder, &1)?
+        );
+        // The trees do not share their nodes.
+        mapped.get_node_by_id(&3).unwrap().set_value(Some(0));
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value(), Some(6));
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_ids() -> Result<()> {
+        let tree = sample_tree()?;
+        let mapped = tree.map_ids(|node_id| node_id * 10)?;
+        mapped.debug_assert_valid();
+        assert_eq!(
+            mapped.traverse(TraversalStrategy::PreOrder, &10)?,
+            vec![10, 20, 40, 50, 30, 60]
+        );
+        assert_eq!(mapped.get_node_by_id(&40).unwrap().get_value(), Some(5));
+        assert!(matches!(
+            tree.map_ids(|node_id| node_id % 2),
+            Err(DuplicateNodeId(node_id)) if node_id == "1"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter() -> Result<()> {
+        let tree = sample_tree()?;
+        let pruned = tree.filter(is_even, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        pruned.debug_assert_valid();
+        assert_eq!(
+            pruned.traverse(TraversalStrategy::PreOrder, &1)?,
+            vec![1, 3]
+        );
+
+        let spliced = tree.filter(is_even, NodeRemovalStrategy::RetainChildren)?;
+        spliced.debug_assert_valid();
+        assert_eq!(
+            spliced.traverse(TraversalStrategy::PreOrder, &1)?,
+            vec![1, 5, 3]
+        );
+        assert_eq!(
+            spliced.get_node_by_id(&1).unwrap().get_children_ids(),
+            vec![5, 3]
+        );
+        assert_eq!(tree.get_nodes().len(), 6);
+
+        let empty = tree.filter(|_| false, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert!(empty.get_nodes().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter_root() -> Result<()> {
+        let tree = sample_tree()?;
+        let is_child = |node: &Node<i32, i32>| node.get_node_id() != 1;
+        assert!(tree
+            .filter(is_child, NodeRemovalStrategy::RetainChildren)
+            .is_err());
+
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), Some(&1))?;
+        forest.add_node(Node::new(3, Some(6)), Some(&1))?;
+        forest.add_node(Node::new(4, Some(4)), None)?;
+        let filtered = forest.filter(is_child, NodeRemovalStrategy::RetainChildren)?;
+        filtered.debug_assert_valid();
+        assert_eq!(
+            filtered
+                .get_root_nodes()
+                .iter()
+                .map(|node| node.get_node_id())
+                .collect::<Vec<_>>(),
+            vec![2, 3, 4]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fold() -> Result<()> {
+        let tree = sample_tree()?;
+        let height = tree.fold(&1, |_, children: Vec<i32>| {
+            children.into_iter().max().map_or(0, |height| height + 1)
+        })?;
+        assert_eq!(height, tree.get_height()?);
+        let leaves = tree.fold(&2, |node, children: Vec<Vec<i32>>| {
+            if children.is_empty() {
+                vec![node.get_node_id()]
+            } else {
+                children.concat()
+            }
+        })?;
+        assert_eq!(leaves, vec![4, 5]);
+        assert!(tree.fold(&7, |_, _: Vec<i32>| 0).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,720 @@
+use crate::lib::*;
+use crate::prelude::Tree;
+use crate::tree::{IdMap, IdSet};
+
+/// A violation of the integrity of a tree.
+///
+/// Violations are reported by [`Tree::validate`] and fixed by [`Tree::repair`]. They usually happen
+/// when the links of nodes that are part of a tree are changed directly through methods like
+/// [`Node::add_child`](crate::prelude::Node::add_child), [`Node::set_parent`](crate::prelude::Node::set_parent)
+/// or [`Node::remove_child`](crate::prelude::Node::remove_child). Such changes only reach the node,
+/// while the tree keeps navigating the links it had before.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Violation<Q> {
+    /// More than one node in the tree has this id.
+    DuplicateNodeId(Q),
+    /// The parent of the node is not in the tree.
+    MissingParent {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the parent that is not in the tree.
+        parent_id: Q,
+    },
+    /// A child of the node is not in the tree.
+    MissingChild {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the child that is not in the tree.
+        child_id: Q,
+    },
+    /// The node lists the same child more than once.
+    DuplicateChild {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the child that is listed more than once.
+        child_id: Q,
+    },
+    /// The node lists a child whose parent is another node.
+    ChildNotLinked {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the child.
+        child_id: Q,
+    },
+    /// The parent of the node does not list the node as a child.
+    ParentNotLinked {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the parent.
+        parent_id: Q,
+    },
+    /// The nodes form a cycle when following their parents.
+    Cycle(Vec<Q>),
+    /// More than one node in the tree has no parent.
+    MultipleRoots(Vec<Q>),
+    /// The tree has nodes but none of them is a root node.
+    NoRootNode,
+    /// The parent or children of the node were changed directly on the node, so they no longer match
+    /// the links the tree navigates.
+    LinksChanged(Q),
+    /// The value of the node was set to a key held by another node in a unique value index.
+    DuplicateKey {
+        /// The id of the node.
+        node_id: Q,
+        /// The id of the node holding the key in the index.
+        holder_id: Q,
+    },
+}
+
+impl<Q> Display for Violation<Q>
+where
+    Q: Display,
+{
+    /// Display the violation.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Violation::DuplicateNodeId(node_id) => {
+                write!(f, "Node {} is present more than once.", node_id)
+            }
+            Violation::MissingParent { node_id, parent_id } => {
+                write!(
+                    f,
+                    "Parent {} of node {} is not in the tree.",
+                    parent_id, node_id
+                )
+            }
+            Violation::MissingChild { node_id, child_id } => {
+                write!(
+                    f,
+                    "Child {} of node {} is not in the tree.",
+                    child_id, node_id
+                )
+            }
+            Violation::DuplicateChild { node_id, child_id } => {
+                write!(
+                    f,
+                    "Node {} lists child {} more than once.",
+                    node_id, child_id
+                )
+            }
+            Violation::ChildNotLinked { node_id, child_id } => write!(
+                f,
+                "Node {} lists child {} but the child has another parent.",
+                node_id, child_id
+            ),
+            Violation::ParentNotLinked { node_id, parent_id } => write!(
+                f,
+                "Parent {} of node {} does not list the node as a child.",
+                parent_id, node_id
+            ),
+            Violation::Cycle(node_ids) => {
+                write!(f, "Nodes ")?;
+                write_ids(f, node_ids)?;
+                write!(f, " form a cycle.")
+            }
+            Violation::MultipleRoots(node_ids) => {
+                write!(f, "Nodes ")?;
+                write_ids(f, node_ids)?;
+                write!(f, " are all root nodes.")
+            }
+            Violation::NoRootNode => write!(f, "The tree has no root node."),
+            Violation::LinksChanged(node_id) => write!(
+                f,
+                "The links of node {} were changed outside of the tree.",
+                node_id
+            ),
+            Violation::DuplicateKey { node_id, holder_id } => write!(
+                f,
+                "Node {} has the same key as node {} in a unique value index.",
+                node_id, holder_id
+            ),
+        }
+    }
+}
+
+/// Write a comma separated list of node ids.
+fn write_ids<Q: Display>(f: &mut Formatter<'_>, node_ids: &[Q]) -> FmtResult {
+    for (i, node_id) in node_ids.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", node_id)?;
+    }
+    Ok(())
+}
+
+/// The policy used by [`Tree::repair`] for nodes that cannot be reached from the root node.
+///
+/// Nodes become unreachable when their parent is not in the tree, when they are part of a cycle or
+/// when they are an extra root node. Forests keep these nodes as root nodes instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RepairPolicy {
+    /// Attach the unreachable nodes, together with their descendants, as children of the root node.
+    AttachToRoot,
+    /// Remove the unreachable nodes together with their descendants.
+    RemoveUnreachable,
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Validate the integrity of the tree.
+    ///
+    /// This method checks that every node id is unique, that the parent and children links of the
+    /// nodes point at nodes in the tree and agree with each other, that there are no cycles and that
+    /// the tree has exactly one root node. A forest may have any number of root nodes. It also checks
+    /// that the links of the nodes still match the links of the tree and that no two nodes share a key
+    /// in a unique value index of the tree.
+    ///
+    /// # Returns
+    ///
+    /// All the violations found in the tree. The list is empty if the tree is valid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree, Violation};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// assert!(tree.validate().is_empty());
+    ///
+    /// // Unlink the child from outside the tree.
+    /// tree.get_node_by_id(&node_2).unwrap().set_parent(None);
+    /// assert!(tree.validate().contains(&Violation::ChildNotLinked { node_id: 1, child_id: 2 }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Vec<Violation<Q>> {
+        let mut violations = vec![];
+        let mut seen = IdSet::new();
+        let mut unique = vec![];
+        for node in self.arena.nodes().iter() {
+            let node_id = node.get_node_id();
+            if seen.insert(node_id.clone()) {
+                unique.push(node);
+            } else {
+                violations.push(Violation::DuplicateNodeId(node_id));
+            }
+        }
+        let nodes = unique
+            .iter()
+            .map(|node| (node.get_node_id(), *node))
+            .collect::<IdMap<_, _>>();
+
+        let mut roots = vec![];
+        for node in unique.iter() {
+            let node_id = node.get_node_id();
+            let mut children = IdSet::new();
+            for child_id in node.get_children_ids() {
+                if !children.insert(child_id.clone()) {
+                    violations.push(Violation::DuplicateChild {
+                        node_id: node_id.clone(),
+                        child_id,
+                    });
+                    continue;
+                }
+                match nodes.get(&child_id) {
+                    None => violations.push(Violation::MissingChild {
+                        node_id: node_id.clone(),
+                        child_id,
+                    }),
+                    Some(child) if child.get_parent_id().as_ref() != Some(&node_id) => violations
+                        .push(Violation::ChildNotLinked {
+                            node_id: node_id.clone(),
+                            child_id,
+                        }),
+                    _ => {}
+                }
+            }
+            if self.links_changed(&node_id) {
+                violations.push(Violation::LinksChanged(node_id.clone()));
+            }
+            match node.get_parent_id() {
+                None => roots.push(node_id),
+                Some(parent_id) => match nodes.get(&parent_id) {
+                    None => violations.push(Violation::MissingParent { node_id, parent_id }),
+                    Some(parent) if !parent.get_children_ids().contains(&node_id) => {
+                        violations.push(Violation::ParentNotLinked { node_id, parent_id })
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        let parents = unique
+            .iter()
+            .map(|node| (node.get_node_id(), node.get_parent_id()))
+            .collect::<Vec<_>>();
+        violations.extend(find_cycles(&parents).into_iter().map(Violation::Cycle));
+
+        if roots.len() > 1 && !self.forest {
+            violations.push(Violation::MultipleRoots(roots));
+        } else if roots.is_empty() && self.arena.len() > 0 {
+            violations.push(Violation::NoRootNode);
+        }
+
+        for index in self.value_indexes.upgrade() {
+            violations.extend(
+                index
+                    .find_duplicates()
+                    .into_iter()
+                    .map(|(node_id, holder_id)| Violation::DuplicateKey { node_id, holder_id }),
+            );
+        }
+        violations
+    }
+
+    /// Check if the links held by a node differ from the links of the tree. Links to nodes that are not
+    /// in the tree are left out, since the tree cannot hold them.
+    #[doc(hidden)]
+    fn links_changed(&self, node_id: &Q) -> bool {
+        let Some(handle) = self.arena.get_handle(node_id) else {
+            return false;
+        };
+        let Some(node) = self.arena.get(handle) else {
+            return false;
+        };
+        let parent_id = node
+            .get_parent_id()
+            .filter(|parent_id| self.arena.contains(parent_id));
+        let children_ids = node
+            .get_children_ids()
+            .into_iter()
+            .filter(|child_id| self.arena.contains(child_id))
+            .collect::<Vec<_>>();
+        parent_id
+            != self
+                .arena
+                .parent(handle)
+                .and_then(|parent| self.id_of(parent))
+            || children_ids != self.children_ids(handle)
+    }
+
+    /// Repair the integrity of the tree.
+    ///
+    /// This method fixes the violations reported by [`Tree::validate`]. The links held by the nodes win
+    /// over the links of the tree, so the tree is rebuilt from them:
+    /// - Only the first node with a duplicate id is kept.
+    /// - Links to nodes that are not in the tree are dropped.
+    /// - The parent of a node wins over the children lists of the other nodes. A node without a valid
+    ///   parent is adopted by the first node that lists it as a child.
+    /// - Cycles are broken by unlinking one node of the cycle from its parent.
+    /// - The first root node is kept as the root node of the tree and all the other nodes that cannot
+    ///   be reached from it are handled using the given policy. In a forest every root node is kept.
+    ///
+    /// The nodes that are kept keep their handles, while the handles of the dropped nodes no longer
+    /// resolve.
+    ///
+    /// Nodes sharing a key in a unique value index are left as they are: only their values can settle
+    /// which node holds the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - What to do with the nodes that cannot be reached from the root node.
+    ///
+    /// # Returns
+    ///
+    /// The violations that were found in the tree before it was repaired.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, RepairPolicy, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(6)), Some(&node_2))?;
+    ///
+    /// // Detach a node from outside the tree.
+    /// tree.get_node_by_id(&node_2).unwrap().remove_child(tree.get_node_by_id(&node_3).unwrap());
+    /// assert!(!tree.validate().is_empty());
+    ///
+    /// tree.repair(RepairPolicy::AttachToRoot);
+    /// assert!(tree.validate().is_empty());
+    /// assert_eq!(tree.get_node_by_id(&node_3).unwrap().get_parent_id(), Some(node_1));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn repair(&mut self, policy: RepairPolicy) -> Vec<Violation<Q>> {
+        let violations = self.validate();
+
+        // Keep the first node with every id.
+        let mut seen = IdSet::new();
+        let (handles, nodes): (Vec<_>, Vec<_>) = self
+            .arena
+            .handles()
+            .filter_map(|handle| self.arena.get(handle).map(|node| (handle, node.clone())))
+            .filter(|(_, node)| seen.insert(node.get_node_id()))
+            .unzip();
+
+        // Settle the parent of every node.
+        let mut listed_by = IdMap::new();
+        for node in nodes.iter() {
+            let node_id = node.get_node_id();
+            for child_id in node.get_children_ids() {
+                if child_id != node_id && seen.contains(&child_id) {
+                    listed_by.entry(child_id).or_insert_with(|| node_id.clone());
+                }
+            }
+        }
+        for node in nodes.iter() {
+            let node_id = node.get_node_id();
+            let parent_id = node
+                .get_parent_id()
+                .filter(|parent_id| parent_id != &node_id && seen.contains(parent_id))
+                .or_else(|| listed_by.get(&node_id).cloned());
+            node.set_parent_id(parent_id);
+        }
+
+        // Break the cycles.
+        let parents = nodes
+            .iter()
+            .map(|node| (node.get_node_id(), node.get_parent_id()))
+            .collect::<Vec<_>>();
+        let positions = nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.get_node_id(), i))
+            .collect::<IdMap<_, _>>();
+        for cycle in find_cycles(&parents) {
+            if let Some(position) = cycle.first().and_then(|node_id| positions.get(node_id)) {
+                nodes[*position].set_parent_id(None);
+            }
+        }
+
+        // Rebuild the children lists from the parents, keeping the existing order of the children.
+        let mut children = IdMap::new();
+        let mut linked = IdSet::new();
+        for node in nodes.iter() {
+            let node_id = node.get_node_id();
+            for child_id in node.get_children_ids() {
+                let is_child = positions
+                    .get(&child_id)
+                    .is_some_and(|i| nodes[*i].get_parent_id().as_ref() == Some(&node_id));
+                if is_child && linked.insert(child_id.clone()) {
+                    children
+                        .entry(node_id.clone())
+                        .or_insert_with(Vec::new)
+                        .push(child_id);
+                }
+            }
+        }
+        for node in nodes.iter() {
+            let node_id = node.get_node_id();
+            if let Some(parent_id) = node.get_parent_id() {
+                if linked.insert(node_id.clone()) {
+                    children
+                        .entry(parent_id)
+                        .or_insert_with(Vec::new)
+                        .push(node_id);
+                }
+            }
+        }
+        for node in nodes.iter() {
+            node.set_children_ids(children.remove(&node.get_node_id()).unwrap_or_default());
+        }
+
+        // Handle the nodes that cannot be reached from the root node.
+        let roots = nodes
+            .iter()
+            .filter(|node| node.get_parent_id().is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut removed = IdSet::new();
+        if let Some((root, unreachable)) = roots.split_first().filter(|_| !self.forest) {
+            match policy {
+                RepairPolicy::AttachToRoot => {
+                    for node in unreachable {
+                        root.add_child(node.clone());
+                    }
+                }
+                RepairPolicy::RemoveUnreachable => {
+                    let mut stack = unreachable
+                        .iter()
+                        .map(|node| node.get_node_id())
+                        .collect::<Vec<_>>();
+                    while let Some(node_id) = stack.pop() {
+                        if let Some(i) = positions.get(&node_id) {
+                            stack.extend(nodes[*i].get_children_ids());
+                        }
+                        removed.insert(node_id);
+                    }
+                }
+            }
+        }
+
+        // Drop the duplicate and removed nodes, then relink the other nodes in place so that their
+        // handles keep resolving.
+        let kept = handles
+            .into_iter()
+            .zip(nodes.iter())
+            .filter(|(_, node)| !removed.contains(&node.get_node_id()))
+            .map(|(handle, _)| handle)
+            .collect::<Vec<_>>();
+        let kept_handles = kept.iter().copied().collect::<IdSet<_>>();
+        let dropped = self
+            .arena
+            .handles()
+            .filter(|handle| !kept_handles.contains(handle))
+            .collect::<Vec<_>>();
+        for node in self.arena.nodes().iter() {
+            self.untrack_node(node);
+        }
+        self.arena.remove_all(&dropped);
+        for handle in kept {
+            self.arena.link_from_ids(handle);
+            if let Some(node) = self.arena.get(handle) {
+                self.track_node(node);
+            }
+        }
+        violations
+    }
+
+    /// Assert that the tree is valid in debug builds.
+    ///
+    /// This method panics with the list of violations reported by [`Tree::validate`] if the tree is
+    /// not valid. It does nothing in release builds, so it can be called after every mutation in tests
+    /// and debug builds without slowing down release builds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the tree is not valid and debug assertions are enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, i32> = Tree::new(Some("Sample Tree"));
+    /// let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+    /// tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+    /// tree.debug_assert_valid();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn debug_assert_valid(&self) {
+        if cfg!(debug_assertions) {
+            let violations = self.validate();
+            if !violations.is_empty() {
+                let violations = violations
+                    .iter()
+                    .map(|violation| violation.to_string())
+                    .collect::<Vec<_>>();
+                panic!("Tree integrity violated: {}", violations.join(" "));
+            }
+        }
+    }
+}
+
+/// Find the cycles formed by following the parents of the nodes.
+///
+/// Every cycle is returned once, starting from the node of the cycle that is reached first. Parents
+/// that are not in the list are treated as missing and end the walk.
+fn find_cycles<Q>(parents: &[(Q, Option<Q>)]) -> Vec<Vec<Q>>
+where
+    Q: PartialEq + Eq + Clone + Hash + Ord,
+{
+    let parent_of = parents.iter().cloned().collect::<IdMap<_, _>>();
+    let mut visited = IdSet::new();
+    let mut cycles = vec![];
+    for (node_id, _) in parents.iter() {
+        let mut path = vec![];
+        let mut on_path = IdSet::new();
+        let mut current = Some(node_id.clone());
+        while let Some(node_id) = current {
+            if visited.contains(&node_id) {
+                if on_path.contains(&node_id) {
+                    let start = path.iter().position(|x| x == &node_id).unwrap_or(0);
+                    cycles.push(path[start..].to_vec());
+                }
+                break;
+            }
+            visited.insert(node_id.clone());
+            on_path.insert(node_id.clone());
+            current = parent_of.get(&node_id).cloned().flatten();
+            path.push(node_id);
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::{Node, Result, TraversalStrategy::PreOrder};
+    use crate::tree::tests::sample_tree;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_tree() -> Result<()> {
+        let tree = sample_tree()?;
+        assert!(tree.validate().is_empty());
+        tree.debug_assert_valid();
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_asymmetric_links() -> Result<()> {
+        let tree = sample_tree()?;
+        tree.get_node_by_id(&4).unwrap().set_parent_id(Some(3));
+        tree.get_node_by_id(&1)
+            .unwrap()
+            .add_child(Node::new(7, Some(8)));
+        assert_eq!(
+            tree.validate(),
+            vec![
+                Violation::MissingChild {
+                    node_id: 1,
+                    child_id: 7
+                },
+                Violation::ChildNotLinked {
+                    node_id: 2,
+                    child_id: 4
+                },
+                Violation::LinksChanged(4),
+                Violation::ParentNotLinked {
+                    node_id: 4,
+                    parent_id: 3
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_cycle_and_roots() -> Result<()> {
+        let tree = sample_tree()?;
+        tree.get_node_by_id(&1).unwrap().set_parent_id(Some(4));
+        tree.get_node_by_id(&4).unwrap().set_children_ids(vec![1]);
+        let violations = tree.validate();
+        assert!(violations.contains(&Violation::Cycle(vec![1, 4, 2])));
+        assert!(violations.contains(&Violation::NoRootNode));
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_links_changed() -> Result<()> {
+        let mut tree = sample_tree()?;
+        let node_4 = tree.get_node_by_id(&4).unwrap();
+        tree.get_node_by_id(&2)
+            .unwrap()
+            .remove_child(node_4.clone());
+        tree.get_node_by_id(&3).unwrap().add_child(node_4);
+        // The tree keeps navigating its own links until it is repaired.
+        assert_eq!(tree.traverse(PreOrder, &1)?, vec![1, 2, 4, 5, 3, 6]);
+        assert_eq!(
+            tree.validate(),
+            vec![
+                Violation::LinksChanged(2),
+                Violation::LinksChanged(3),
+                Violation::LinksChanged(4)
+            ]
+        );
+        tree.repair(RepairPolicy::AttachToRoot);
+        tree.debug_assert_valid();
+        assert_eq!(tree.traverse(PreOrder, &1)?, vec![1, 2, 5, 3, 6, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_attach_to_root() -> Result<()> {
+        let mut tree = sample_tree()?;
+        tree.get_node_by_id(&2)
+            .unwrap()
+            .remove_child(tree.get_node_by_id(&4).unwrap());
+        tree.get_node_by_id(&3).unwrap().set_parent_id(Some(2));
+        let violations = tree.repair(RepairPolicy::AttachToRoot);
+        assert!(violations.contains(&Violation::MultipleRoots(vec![1, 4])));
+        tree.debug_assert_valid();
+        assert_eq!(
+            tree.get_node_by_id(&1).unwrap().get_children_ids(),
+            vec![2, 4]
+        );
+        assert_eq!(
+            tree.get_node_by_id(&2).unwrap().get_children_ids(),
+            vec![5, 3]
+        );
+        let handle = tree.get_node_handle(&4).unwrap();
+        assert_eq!(tree.get_parent_handle(handle), tree.get_node_handle(&1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_remove_unreachable() -> Result<()> {
+        let mut tree = sample_tree()?;
+        tree.get_node_by_id(&2).unwrap().set_parent_id(Some(4));
+        tree.get_node_by_id(&4).unwrap().set_children_ids(vec![2]);
+        tree.repair(RepairPolicy::RemoveUnreachable);
+        tree.debug_assert_valid();
+        assert_eq!(tree.get_nodes().len(), 3);
+        assert_eq!(tree.get_node_by_id(&1).unwrap().get_children_ids(), vec![3]);
+        assert!(tree.get_node_by_id(&4).is_none());
+        assert!(tree.get_node_by_id(&5).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_keeps_handles() -> Result<()> {
+        let mut tree = sample_tree()?;
+        let handles = [1, 2, 3, 4].map(|node_id| tree.get_node_handle(&node_id).unwrap());
+        tree.get_node_by_id(&2).unwrap().set_parent_id(Some(4));
+        tree.get_node_by_id(&4).unwrap().set_children_ids(vec![2]);
+        tree.repair(RepairPolicy::RemoveUnreachable);
+        let node_id = |handle| {
+            tree.get_node_by_handle(handle)
+                .map(|node| node.get_node_id())
+        };
+        assert_eq!(handles.map(node_id), [Some(1), None, Some(3), None]);
+        assert_eq!(
+            tree.get_children_handles(handles[0])
+                .map(Iterator::collect::<Vec<_>>),
+            Some(vec![handles[2]])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_duplicate_node_id() -> Result<()> {
+        let mut nodes = sample_tree()?.get_nodes().clone();
+        nodes.push(Node::new(3, Some(8)));
+        let mut tree = Tree::from_unchecked_parts(Some("Sample Tree"), nodes, false);
+        assert_eq!(tree.validate(), vec![Violation::DuplicateNodeId(3)]);
+        tree.repair(RepairPolicy::RemoveUnreachable);
+        tree.debug_assert_valid();
+        assert_eq!(tree.get_nodes().len(), 6);
+        assert_eq!(tree.get_node_by_id(&3).unwrap().get_value(), Some(6));
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_forest_keeps_roots() -> Result<()> {
+        let mut forest = Tree::new_forest(Some("Sample Forest"));
+        forest.add_node(Node::new(1, Some(2)), None)?;
+        forest.add_node(Node::new(2, Some(3)), Some(&1))?;
+        forest.add_node(Node::new(3, Some(6)), None)?;
+        assert!(forest.validate().is_empty());
+        forest.get_node_by_id(&1).unwrap().set_children_ids(vec![]);
+        forest.get_node_by_id(&2).unwrap().set_parent_id(Some(4));
+        forest.repair(RepairPolicy::RemoveUnreachable);
+        forest.debug_assert_valid();
+        assert_eq!(forest.get_nodes().len(), 3);
+        assert_eq!(forest.get_root_nodes().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_debug_assert_valid_panics() {
+        let tree = sample_tree().unwrap();
+        tree.get_node_by_id(&4).unwrap().set_parent(None);
+        tree.debug_assert_valid();
+    }
+}
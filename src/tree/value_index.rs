@@ -0,0 +1,653 @@
+use crate::error::Error::InvalidOperation;
+use crate::lib::*;
+use crate::node::{Lock, MaybeSync, ValueWatcher};
+use crate::prelude::{Node, Result, Tree};
+use crate::tree::IdMap;
+
+/// The shared pointer holding the state of a value index.
+#[cfg(not(feature = "async"))]
+type Shared<V> = Rc<V>;
+
+/// The shared pointer holding the state of a value index.
+#[cfg(feature = "async")]
+type Shared<V> = Arc<V>;
+
+/// The number of nodes a key of a value index can map to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IndexMode {
+    /// Every key maps to at most one node. Adding a node whose key is already taken by another node
+    /// of the tree fails. Setting the value of a node to a key that is already taken cannot fail: the
+    /// key stays with the node that held it and the clash is reported by [`Tree::validate`] as a
+    /// [`Violation::DuplicateKey`](crate::prelude::Violation::DuplicateKey).
+    Unique,
+    /// A key can map to any number of nodes.
+    Multi,
+}
+
+/// A secondary index of a tree from the values of the nodes to their ids.
+///
+/// A value index is created with [`Tree::create_value_index`] or [`Tree::create_value_index_by`] and
+/// is kept up to date by the tree as nodes are added and removed, and by the nodes whenever their value
+/// is set. Looking nodes up by key takes constant time (`HashMap`) or logarithmic time (`BTreeMap`
+/// under `no_std`). Nodes without a value are not indexed.
+///
+/// The index belongs to the tree it was created on: clones of the tree do not maintain it. The index
+/// stops being maintained once every copy of the handle has been dropped.
+///
+/// # Type Parameters
+///
+/// * `Q` - The type of the node id.
+/// * `T` - The type of the node value.
+/// * `K` - The type of the key extracted from the node values, the value itself by default.
+///
+/// # Example
+///
+/// ```rust
+/// # use tree_ds::prelude::{IndexMode, Node, Tree};
+///
+/// # fn main() -> tree_ds::prelude::Result<()> {
+/// let mut tree: Tree<i32, &str> = Tree::new(Some("Employees"));
+/// let index = tree.create_value_index(IndexMode::Unique)?;
+/// let node_1 = tree.add_node(Node::new(1, Some("Harry Doe")), None)?;
+/// let node_2 = tree.add_node(Node::new(2, Some("Jane Doe")), Some(&node_1))?;
+/// assert_eq!(index.get(&"Jane Doe"), Some(node_2));
+///
+/// tree.get_node_by_id(&node_2).unwrap().set_value(Some("Jane Smith"));
+/// assert_eq!(index.get(&"Jane Doe"), None);
+/// assert_eq!(index.get(&"Jane Smith"), Some(node_2));
+/// # Ok(())
+/// # }
+/// ```
+pub struct ValueIndex<Q, T, K = T>(Shared<IndexState<Q, T, K>>);
+
+/// The state of a value index shared by its handles, the tree and the nodes.
+#[doc(hidden)]
+struct IndexState<Q, T, K> {
+    /// Whether a key can map to more than one node.
+    mode: IndexMode,
+    /// The function extracting the key from the value of a node.
+    key: Box<dyn KeyFn<T, K>>,
+    /// The ids of the nodes holding every key, in the order they were indexed.
+    entries: Lock<IdMap<K, Vec<Q>>>,
+}
+
+/// A function extracting the key of a value index from the value of a node.
+#[doc(hidden)]
+trait KeyFn<T, K>: Fn(&T) -> K + MaybeSync {}
+
+impl<T, K, F> KeyFn<T, K> for F where F: Fn(&T) -> K + MaybeSync {}
+
+impl<Q, T, K> ValueIndex<Q, T, K>
+where
+    Q: PartialEq + Eq + Clone,
+    K: Hash + Ord,
+{
+    /// Get the id of the node holding a key.
+    ///
+    /// This method returns the id of the node whose value maps to the given key. When more than one
+    /// node holds the key, the node that was indexed first is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// The id of the node holding the key, or `None` if no node holds it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndexMode, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Employees"));
+    /// let node_1 = tree.add_node(Node::new(1, Some("Harry Doe")), None)?;
+    /// let index = tree.create_value_index(IndexMode::Unique)?;
+    /// assert_eq!(index.get(&"Harry Doe"), Some(node_1));
+    /// assert_eq!(index.get(&"Jane Doe"), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get(&self, key: &K) -> Option<Q> {
+        self.0
+            .entries
+            .borrow()
+            .get(key)
+            .and_then(|node_ids| node_ids.first().cloned())
+    }
+
+    /// Get the ids of all the nodes holding a key.
+    ///
+    /// This method returns the ids of the nodes whose value maps to the given key in the order they
+    /// were indexed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// The ids of the nodes holding the key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndexMode, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Employees"));
+    /// let index = tree.create_value_index_by(IndexMode::Multi, |name: &&str| name.len())?;
+    /// let node_1 = tree.add_node(Node::new(1, Some("Harry Doe")), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some("Jane Doe")), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some("John Doe")), Some(&node_1))?;
+    /// assert_eq!(index.get_all(&8), vec![node_2, node_3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_all(&self, key: &K) -> Vec<Q> {
+        self.0
+            .entries
+            .borrow()
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Check if a node holds a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Returns
+    ///
+    /// `true` if at least one node holds the key, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndexMode, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Employees"));
+    /// let index = tree.create_value_index(IndexMode::Unique)?;
+    /// tree.add_node(Node::new(1, Some("Harry Doe")), None)?;
+    /// assert!(index.contains_key(&"Harry Doe"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.0.entries.borrow().contains_key(key)
+    }
+
+    /// Get the mode of the index.
+    ///
+    /// # Returns
+    ///
+    /// Whether a key of the index can map to more than one node.
+    pub fn get_mode(&self) -> IndexMode {
+        self.0.mode
+    }
+}
+
+impl<Q, T, K> Clone for ValueIndex<Q, T, K> {
+    /// Get another handle to the same index.
+    fn clone(&self) -> Self {
+        ValueIndex(self.0.clone())
+    }
+}
+
+impl<Q, T, K> Debug for ValueIndex<Q, T, K>
+where
+    Q: Debug,
+    K: Debug,
+{
+    /// Format the index for debugging. The key extractor is left out.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ValueIndex")
+            .field("mode", &self.0.mode)
+            .field("entries", &self.0.entries.borrow())
+            .finish()
+    }
+}
+
+impl<Q, T, K> ValueWatcher<Q, T> for IndexState<Q, T, K>
+where
+    Q: PartialEq + Eq + Clone + MaybeSync,
+    T: PartialEq + Eq + Clone,
+    K: Hash + Ord + MaybeSync,
+{
+    fn value_added(&self, node_id: &Q, value: Option<&T>) {
+        let Some(value) = value else {
+            return;
+        };
+        // A value set on a node of a unique index can clash with the node holding the key. The key stays
+        // with that node and the clash is reported by `Tree::validate`.
+        self.entries
+            .borrow_mut()
+            .entry((self.key)(value))
+            .or_default()
+            .push(node_id.clone());
+    }
+
+    fn value_removed(&self, node_id: &Q, value: Option<&T>) {
+        let Some(value) = value else {
+            return;
+        };
+        let key = (self.key)(value);
+        let mut entries = self.entries.borrow_mut();
+        if let Some(node_ids) = entries.get_mut(&key) {
+            if let Some(position) = node_ids.iter().position(|id| id == node_id) {
+                node_ids.remove(position);
+            }
+            if node_ids.is_empty() {
+                entries.remove(&key);
+            }
+        }
+    }
+
+    fn find_conflict(&self, nodes: &[Node<Q, T>]) -> Option<(Q, Q)> {
+        if self.mode == IndexMode::Multi {
+            return None;
+        }
+        // The keys are read before locking the entries: a node notifying its watchers holds its own lock
+        // while it waits for the entries, so the entries must never wait for a node.
+        let keys = nodes
+            .iter()
+            .filter_map(|node| {
+                node.with_value(|value| value.map(&self.key))
+                    .map(|key| (node.get_node_id(), key))
+            })
+            .collect::<Vec<_>>();
+        let entries = self.entries.borrow();
+        let mut added = IdMap::new();
+        for (node_id, key) in keys {
+            let holder = entries
+                .get(&key)
+                .and_then(|node_ids| node_ids.first())
+                .or_else(|| added.get(&key));
+            if let Some(holder) = holder.filter(|holder| *holder != &node_id) {
+                return Some((node_id, holder.clone()));
+            }
+            added.insert(key, node_id);
+        }
+        None
+    }
+
+    fn find_duplicates(&self) -> Vec<(Q, Q)> {
+        if self.mode == IndexMode::Multi {
+            return vec![];
+        }
+        let mut duplicates = vec![];
+        for node_ids in self.entries.borrow().values() {
+            if let Some((holder, others)) = node_ids.split_first() {
+                duplicates.extend(
+                    others
+                        .iter()
+                        .map(|node_id| (node_id.clone(), holder.clone())),
+                );
+            }
+        }
+        duplicates
+    }
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone + Display + Hash + Ord,
+    T: PartialEq + Eq + Clone,
+{
+    /// Create an index of the nodes of the tree by value.
+    ///
+    /// This method indexes the nodes of the tree by their value and keeps the index up to date as
+    /// nodes are added and removed and as their values are set. See [`ValueIndex`].
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Whether a value can be held by more than one node.
+    ///
+    /// # Returns
+    ///
+    /// A handle to the index. An error is returned if the mode is [`IndexMode::Unique`] and more than
+    /// one node of the tree holds the same value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndexMode, Node, Tree};
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, &str> = Tree::new(Some("Employees"));
+    /// let node_1 = tree.add_node(Node::new(1, Some("Harry Doe")), None)?;
+    /// let index = tree.create_value_index(IndexMode::Unique)?;
+    /// assert_eq!(index.get(&"Harry Doe"), Some(node_1));
+    ///
+    /// // The value is already held by another node.
+    /// assert!(tree.add_node(Node::new(2, Some("Harry Doe")), Some(&node_1)).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_value_index(&mut self, mode: IndexMode) -> Result<ValueIndex<Q, T>>
+    where
+        Q: MaybeSync + 'static,
+        T: Hash + Ord + MaybeSync + 'static,
+    {
+        self.create_value_index_by(mode, T::clone)
+    }
+
+    /// Create an index of the nodes of the tree by a key extracted from their value.
+    ///
+    /// This method indexes the nodes of the tree by the key the given function extracts from their
+    /// value and keeps the index up to date as nodes are added and removed and as their values are
+    /// set. See [`ValueIndex`].
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Whether a key can be held by more than one node.
+    /// * `key` - The function extracting the key from the value of a node.
+    ///
+    /// # Returns
+    ///
+    /// A handle to the index. An error is returned if the mode is [`IndexMode::Unique`] and more than
+    /// one node of the tree holds the same key.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use tree_ds::prelude::{IndexMode, Node, Tree};
+    ///
+    /// #[derive(Clone, Debug, PartialEq, Eq)]
+    /// struct Employee {
+    ///     name: String,
+    ///     team: String,
+    /// }
+    ///
+    /// # fn main() -> tree_ds::prelude::Result<()> {
+    /// let mut tree: Tree<i32, Employee> = Tree::new(Some("Employees"));
+    /// let by_team = tree.create_value_index_by(IndexMode::Multi, |employee: &Employee| {
+    ///     employee.team.clone()
+    /// })?;
+    /// let employee = |name: &str, team: &str| Employee {
+    ///     name: name.to_string(),
+    ///     team: team.to_string(),
+    /// };
+    /// let node_1 = tree.add_node(Node::new(1, Some(employee("Harry Doe", "Board"))), None)?;
+    /// let node_2 = tree.add_node(Node::new(2, Some(employee("Jane Doe", "Sales"))), Some(&node_1))?;
+    /// let node_3 = tree.add_node(Node::new(3, Some(employee("John Doe", "Sales"))), Some(&node_1))?;
+    /// assert_eq!(by_team.get_all(&String::from("Sales")), vec![node_2, node_3]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn create_value_index_by<K, F>(
+        &mut self,
+        mode: IndexMode,
+        key: F,
+    ) -> Result<ValueIndex<Q, T, K>>
+    where
+        Q: MaybeSync + 'static,
+        T: 'static,
+        K: Hash + Ord + MaybeSync + 'static,
+        F: Fn(&T) -> K + MaybeSync + 'static,
+    {
+        let state = Shared::new(IndexState {
+            mode,
+            key: Box::new(key),
+            entries: Lock::new(IdMap::new()),
+        });
+        let watcher: Shared<dyn ValueWatcher<Q, T>> = state.clone();
+        let nodes = self.arena.nodes().iter().cloned().collect::<Vec<_>>();
+        if let Some((node_id, holder_id)) = watcher.find_conflict(&nodes) {
+            return Err(InvalidOperation(format!(
+                "Node {} has the same key as node {} in a unique value index.",
+                node_id, holder_id
+            )));
+        }
+        let watcher = Shared::downgrade(&watcher);
+        for node in nodes.iter() {
+            node.with_value(|value| state.value_added(&node.get_node_id(), value));
+            node.watch(&watcher);
+        }
+        self.value_indexes.add(&watcher);
+        Ok(ValueIndex(state))
+    }
+
+    /// Check that nodes can be added to the tree without breaking a unique value index.
+    #[doc(hidden)]
+    pub(crate) fn check_value_indexes(&self, nodes: &[Node<Q, T>]) -> Result<()> {
+        for watcher in self.value_indexes.upgrade() {
+            if let Some((node_id, holder_id)) = watcher.find_conflict(nodes) {
+                return Err(InvalidOperation(format!(
+                    "Node {} has the same key as node {} in a unique value index.",
+                    node_id, holder_id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<Q, T> Tree<Q, T>
+where
+    Q: PartialEq + Eq + Clone,
+    T: PartialEq + Eq + Clone,
+{
+    /// Add a node of the tree to the value indexes of the tree.
+    #[doc(hidden)]
+    pub(crate) fn track_node(&self, node: &Node<Q, T>) {
+        if self.value_indexes.is_empty() {
+            return;
+        }
+        let node_id = node.get_node_id();
+        for watcher in self.value_indexes.iter() {
+            if let Some(index) = watcher.upgrade() {
+                node.with_value(|value| index.value_added(&node_id, value));
+                node.watch(watcher);
+            }
+        }
+    }
+
+    /// Remove a node of the tree from the value indexes of the tree.
+    #[doc(hidden)]
+    pub(crate) fn untrack_node(&self, node: &Node<Q, T>) {
+        if self.value_indexes.is_empty() {
+            return;
+        }
+        let node_id = node.get_node_id();
+        for watcher in self.value_indexes.iter() {
+            if let Some(index) = watcher.upgrade() {
+                node.with_value(|value| index.value_removed(&node_id, value));
+            }
+            node.unwatch(watcher);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{NodeRemovalStrategy, Violation};
+
+    #[test]
+    fn test_value_index_unique() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        let index = tree.create_value_index(IndexMode::Unique)?;
+        assert_eq!(index.get(&3), Some(2));
+        assert_eq!(index.get_mode(), IndexMode::Unique);
+
+        tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+        assert_eq!(index.get(&4), Some(3));
+        assert!(tree.add_node(Node::new(4, Some(4)), Some(&node_1)).is_err());
+        assert!(tree.get_node_by_id(&4).is_none());
+
+        tree.get_node_by_id(&3).unwrap().set_value(Some(5));
+        assert!(!index.contains_key(&4));
+        assert_eq!(index.get(&5), Some(3));
+
+        // A value set to a key that is already taken leaves the key with its holder.
+        tree.get_node_by_id(&2).unwrap().set_value(Some(5));
+        assert_eq!(index.get(&5), Some(3));
+        assert_eq!(index.get_all(&5), vec![3, 2]);
+        assert_eq!(
+            tree.validate(),
+            vec![Violation::DuplicateKey {
+                node_id: 2,
+                holder_id: 3
+            }]
+        );
+        tree.get_node_by_id(&2).unwrap().set_value(Some(3));
+        assert_eq!(index.get_all(&5), vec![3]);
+
+        tree.remove_node(&3, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(index.get(&5), None);
+        tree.debug_assert_valid();
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_index_unique_conflict() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        tree.add_node(Node::new(2, Some(4)), Some(&node_1))?;
+        assert!(tree.create_value_index(IndexMode::Unique).is_ok());
+        assert!(tree
+            .create_value_index_by(IndexMode::Unique, |value: &i32| value % 2)
+            .is_err());
+        assert!(tree
+            .create_value_index_by(IndexMode::Multi, |value: &i32| value % 2)
+            .is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_index_multi() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let index = tree.create_value_index_by(IndexMode::Multi, |value: &i32| value % 2)?;
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        tree.add_node(Node::new(2, Some(3)), Some(&node_1))?;
+        tree.add_node(Node::new(3, Some(4)), Some(&node_1))?;
+        tree.add_node(Node::new(4, None), Some(&node_1))?;
+        assert_eq!(index.get_all(&0), vec![1, 3]);
+        assert_eq!(index.get_all(&1), vec![2]);
+
+        tree.get_node_by_id(&1).unwrap().set_value(Some(5));
+        assert_eq!(index.get_all(&0), vec![3]);
+        assert_eq!(index.get_all(&1), vec![2, 1]);
+
+        tree.get_node_by_id(&3)
+            .unwrap()
+            .update_value(|value| *value += 1);
+        assert_eq!(index.get_all(&1), vec![2, 1, 3]);
+        tree.get_node_by_id(&3)
+            .unwrap()
+            .with_value_mut(|value| *value = None);
+        assert_eq!(index.get_all(&1), vec![2, 1]);
+
+        let subtree = tree.remove_node(&2, NodeRemovalStrategy::RemoveNodeAndChildren)?;
+        assert_eq!(index.get_all(&1), vec![1]);
+        // The removed nodes are no longer watched by the index.
+        subtree.get_node_by_id(&2).unwrap().set_value(Some(7));
+        assert_eq!(index.get_all(&1), vec![1]);
+
+        tree.add_subtree(&node_1, subtree)?;
+        assert_eq!(index.get_all(&1), vec![1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_index_change_value_in_place() -> Result<()> {
+        use core::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+        static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, PartialEq, Eq)]
+        struct Counted(i32);
+
+        impl Clone for Counted {
+            fn clone(&self) -> Self {
+                CLONES.fetch_add(1, AtomicOrdering::Relaxed);
+                Counted(self.0)
+            }
+        }
+
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(Counted(2))), None)?;
+        tree.add_node(Node::new(2, Some(Counted(3))), Some(&node_1))?;
+        let index = tree.create_value_index_by(IndexMode::Unique, |value: &Counted| value.0)?;
+        let node = tree.get_node_by_id(&node_1).unwrap();
+        let clones = CLONES.load(AtomicOrdering::Relaxed);
+        node.with_value_mut(|value| value.as_mut().unwrap().0 = 4);
+        // The watched value is not copied to tell the index about the change.
+        assert_eq!(CLONES.load(AtomicOrdering::Relaxed), clones);
+        assert_eq!(index.get(&4), Some(1));
+        assert!(!index.contains_key(&2));
+
+        // A value changed to a key that is already taken leaves the key with its holder.
+        node.with_value_mut(|value| value.as_mut().unwrap().0 = 3);
+        assert_eq!(index.get_all(&3), vec![2, 1]);
+        assert!(!index.contains_key(&4));
+        assert_eq!(
+            tree.validate(),
+            vec![Violation::DuplicateKey {
+                node_id: 1,
+                holder_id: 2
+            }]
+        );
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no_std"))]
+    #[test]
+    fn test_value_index_panic_while_changing_value() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let index = tree.create_value_index(IndexMode::Multi)?;
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let node = tree.get_node_by_id(&node_1).unwrap();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            node.update_value(|value| {
+                *value = 3;
+                panic!("The value is half changed.");
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(node.get_value(), Some(3));
+        assert_eq!(index.get_all(&3), vec![1]);
+        assert!(index.get_all(&2).is_empty());
+        tree.debug_assert_valid();
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_index_add_subtree_conflict() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let index = tree.create_value_index(IndexMode::Unique)?;
+        let mut subtree = Tree::new(Some("Sample Subtree"));
+        let node_2 = subtree.add_node(Node::new(2, Some(3)), None)?;
+        subtree.add_node(Node::new(3, Some(3)), Some(&node_2))?;
+        assert!(tree.add_subtree(&node_1, subtree).is_err());
+        assert_eq!(tree.get_nodes().len(), 1);
+        assert_eq!(index.get(&3), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_value_index_dropped() -> Result<()> {
+        let mut tree = Tree::new(Some("Sample Tree"));
+        let node_1 = tree.add_node(Node::new(1, Some(2)), None)?;
+        let index = tree.create_value_index(IndexMode::Unique)?;
+        drop(index);
+        tree.add_node(Node::new(2, Some(2)), Some(&node_1))?;
+
+        let index = tree.create_value_index(IndexMode::Multi)?;
+        let node = tree.get_node_by_id(&node_1).unwrap();
+        drop(tree);
+        // The index is emptied along with the tree.
+        assert!(!index.contains_key(&2));
+        node.set_value(Some(3));
+        assert!(!index.contains_key(&3));
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use tree_ds::prelude::*;
+
+/// Read a Newick text, naming the anonymous nodes `#1`, `#2`, and so on.
+fn read(newick: &str) -> Result<Tree<String, NewickValue>> {
+    let mut next_id = 0;
+    Tree::from_newick(newick, || {
+        next_id += 1;
+        format!("#{}", next_id)
+    })
+}
+
+/// Check that writing a tree and reading it back gives the same tree.
+fn assert_round_trip(newick: &str) -> Result<Tree<String, NewickValue>> {
+    let tree = read(newick)?;
+    tree.debug_assert_valid();
+    let written = tree.to_newick()?;
+    let read_back = read(&written)?;
+    assert_eq!(read_back, tree);
+    assert_eq!(read_back.to_newick()?, written);
+    Ok(tree)
+}
+
+fn value(tree: &Tree<String, NewickValue>, node_id: &str) -> NewickValue {
+    tree.get_node_by_id(&node_id.to_string())
+        .and_then(|node| node.get_value())
+        .unwrap()
+}
+
+#[test]
+fn test_newick_examples() -> Result<()> {
+    // The examples of the Newick format from its description.
+    for newick in [
+        "(,,(,));",
+        "(A,B,(C,D));",
+        "(A,B,(C,D)E)F;",
+        "(:0.1,:0.2,(:0.3,:0.4):0.5);",
+        "(:0.1,:0.2,(:0.3,:0.4):0.5):0.0;",
+        "(A:0.1,B:0.2,(C:0.3,D:0.4):0.5);",
+        "(A:0.1,B:0.2,(C:0.3,D:0.4)E:0.5)F;",
+        "((B:0.2,(C:0.3,D:0.4)E:0.5)F:0.1)A;",
+    ] {
+        let tree = assert_round_trip(newick)?;
+        assert_eq!(tree.to_newick()?, newick);
+    }
+    let tree = read("(,,(,));")?;
+    assert_eq!(tree.get_nodes().len(), 6);
+    assert_eq!(
+        tree.traverse(TraversalStrategy::PreOrder, &String::from("#1"))?,
+        vec!["#1", "#2", "#3", "#4", "#5", "#6"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_newick_phylip() -> Result<()> {
+    let tree = assert_round_trip(include_str!("data/phylip.nwk"))?;
+    assert_eq!(tree.get_nodes().len(), 14);
+    assert_eq!(tree.get_height()?, 4);
+    assert_eq!(value(&tree, "monkey").get_branch_length(), Some(100.8593));
+    assert_eq!(
+        value(&tree, "dog").branch_length.as_deref(),
+        Some("25.46154")
+    );
+    let seal = tree.get_node_by_id(&String::from("seal")).unwrap();
+    assert_eq!(
+        tree.get_ancestor_ids(&seal.get_node_id())?,
+        vec!["#4", "#3", "#1"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_newick_quoted_labels() -> Result<()> {
+    let tree = assert_round_trip(include_str!("data/primates.nwk"))?;
+    let root = tree.get_root_node().unwrap();
+    assert_eq!(root.get_node_id(), "Hominidae");
+    assert_eq!(
+        root.get_value().unwrap().comments,
+        vec!["Primate phylogeny with support values on the internal nodes."]
+    );
+    assert_eq!(
+        root.get_children_ids(),
+        vec!["Homininae 100", "Gorilla gorilla", "Ponginae 98"]
+    );
+    assert_eq!(
+        value(&tree, "Homo sapiens").get_branch_length(),
+        Some(0.0067)
+    );
+    assert!(tree
+        .to_newick()?
+        .starts_with("(('Homo sapiens':0.0067,'Pan troglodytes':0.0072)'Homininae 100':0.0026,"));
+    Ok(())
+}
+
+#[test]
+fn test_newick_nhx_comments() -> Result<()> {
+    let tree = assert_round_trip(include_str!("data/nhx.nwk"))?;
+    assert_eq!(
+        value(&tree, "ADH2").comments,
+        vec!["&&NHX:S=human:E=1.1.1.1"]
+    );
+    let primates = tree
+        .get_node_by_id(&String::from("ADH2"))
+        .and_then(|node| node.get_parent_id())
+        .unwrap();
+    assert_eq!(
+        value(&tree, &primates).comments,
+        vec!["&&NHX:S=Primates:E=1.1.1.1:D=Y:B=100"]
+    );
+    assert_eq!(value(&tree, &primates).get_branch_length(), Some(0.05));
+    assert_eq!(value(&tree, "#1").comments, vec!["&&NHX:E=1.1.1.1:D=N"]);
+    Ok(())
+}
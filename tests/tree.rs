@@ -140,6 +140,7 @@ fn test_tree() -> Result<()> {
     tree.add_subtree(&finance_manager_node, finance_team)?;
     tree.add_subtree(&marketing_manager_node, marketing_team)?;
     tree.add_subtree(&operations_manager_node, operations_team)?;
+    tree.debug_assert_valid();
     // endregion
     println!("{}", tree);
     // region:- Get a subtree from the tree.
@@ -181,6 +182,7 @@ fn test_tree() -> Result<()> {
             .len(),
         5
     );
+    tree.debug_assert_valid();
     // endregion
 
     // region:- Serialize and deserialize the tree.
@@ -194,6 +196,13 @@ fn test_tree() -> Result<()> {
     }
     // endregion
 
+    // region:- Read the tree back from its diagram.
+    let diagram = tree.to_string();
+    let parsed_tree = Tree::<String, String>::from_display_str(&diagram)?;
+    parsed_tree.debug_assert_valid();
+    assert_eq!(parsed_tree.to_string(), diagram);
+    // endregion
+
     // region: -- Traversal
     let traversal = tree.traverse(TraversalStrategy::PreOrder, &finance_manager_node)?;
     traversal.iter().for_each(|node_id| {